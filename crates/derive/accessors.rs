@@ -1,34 +1,71 @@
 use proc_macro2::*;
+use quote::format_ident;
+use syn::ext::IdentExt;
 use syn::*;
 use synstructure::*;
 
-pub fn accessors(s: &Structure, gcs: &[&BindingInfo]) -> TokenStream {
-    let s_ast: &DeriveInput = s.ast();
+use crate::FieldAttr;
 
-    // Collector of bindings with `#[gc]` attributes
+pub fn accessors(s: &Structure) -> TokenStream {
+    let s_ast: &DeriveInput = s.ast();
+    let visibility: &Visibility = &s_ast.vis;
+    let is_enum = matches!(s_ast.data, Data::Enum(_));
 
-    // TODO properly handle enum and tuple structs
-    if gcs.len() > 0 && s.variants().len() != 1 {
-        panic!("gc_accessor attributes can only be used on structs");
-    }
+    let mut accessors = TokenStream::new();
+    for variant in s.variants() {
+        let pat = variant.pat();
 
-    let accessors: TokenStream = gcs.iter().map(|b| {
-        let b_ast: &Field = b.ast();
+        for (idx, b) in variant.bindings().iter().enumerate() {
+            let b_ast: &Field = b.ast();
+            if !matches!(super::checked_field_attr(b_ast), FieldAttr::Accessor) {
+                continue;
+            }
 
-        let field: &Ident = b_ast.ident.as_ref().unwrap();
-        let visibility: &Visibility = &s_ast.vis;
-        let method: &Ident = b_ast.ident.as_ref().unwrap();
+            let ty: &Type = &b_ast.ty;
+            let field_name = match &b_ast.ident {
+                Some(ident) => ident.unraw().to_string(),
+                None => idx.to_string(),
+            };
 
-        let ty: &Type = &b_ast.ty;
+            if is_enum {
+                // Enum accessors are prefixed with the variant and only
+                // return something if `self` is that variant
+                let variant_name = snake_case(&variant.ast().ident.unraw().to_string());
+                let method = format_ident!("{}_{}", variant_name, field_name);
+                accessors.extend(quote! {
+                    #[allow(unused_variables)]
+                    #visibility fn #method<'__root>(self: &'__root elise::Gc<'__root, Self>) -> Option<<#ty as elise::raw::Store<'__root>>::Accessor> {
+                        match **self {
+                            #pat => Some(unsafe { elise::raw::Store::rooted(#b) }),
+                            #[allow(unreachable_patterns)]
+                            _ => None,
+                        }
+                    }
+                });
+            } else {
+                // Tuple struct accessors are named after the field index
+                let (method, member) = match &b_ast.ident {
+                    Some(ident) => (ident.clone(), quote!(#ident)),
+                    None => {
+                        let method = format_ident!("_{}", field_name);
+                        let index = Index::from(idx);
+                        (method, quote!(#index))
+                    }
+                };
+                accessors.extend(quote! {
+                    #visibility fn #method<'__root>(self: &'__root elise::Gc<'__root, Self>) -> <#ty as elise::raw::Store<'__root>>::Accessor {
+                        unsafe {
+                            elise::raw::Store::rooted(&self.#member)
+                        }
+                    }
+                });
 
-        quote! {
-            #visibility fn #method<'__root>(self: &'__root elise::Gc<'__root, Self>) -> <#ty as elise::raw::Store<'__root>>::Accessor {
-                unsafe {
-                    elise::raw::Store::rooted(&self.#field)
+                if is_store_cell(ty) {
+                    accessors.extend(setters(visibility, &field_name, ty, &member));
                 }
             }
         }
-    }).collect();
+    }
 
     // inherent impl with all the accessors
     let (impl_generics, ty_generics, where_clauses) = s_ast.generics.split_for_impl();
@@ -39,3 +76,70 @@ pub fn accessors(s: &Structure, gcs: &[&BindingInfo]) -> TokenStream {
         }
     }
 }
+
+/// Setters for an interior mutable field, which swap the pointer it holds
+fn setters(
+    visibility: &Visibility,
+    field_name: &str,
+    ty: &Type,
+    member: &TokenStream,
+) -> TokenStream {
+    let set = format_ident!("set_{}", field_name);
+    let replace = format_ident!("replace_{}", field_name);
+    let target = quote!(<#ty as elise::raw::StoreCell<'__root>>::Target);
+    quote! {
+        #visibility fn #set<'__root>(self: elise::Gc<'__root, Self>, value: elise::Gc<'__root, #target>) {
+            unsafe {
+                elise::raw::StoreCell::replace(&self.#member, value);
+            }
+        }
+
+        #visibility fn #replace<'__root, '__new>(
+            self: elise::Gc<'__root, Self>,
+            value: elise::Gc<'__root, #target>,
+            root: elise::Root<'__new>,
+        ) -> elise::Gc<'__new, <#target as elise::raw::Reroot<'__new>>::Rerooted> {
+            unsafe {
+                let old = elise::raw::StoreCell::replace(&self.#member, value);
+                root.reroot(elise::Gc::rooted(old))
+            }
+        }
+    }
+}
+
+/// Whether the field is a `PinCell<GcStore<..>>` or a `PinMutex<GcStore<..>>`
+fn is_store_cell(ty: &Type) -> bool {
+    let inner = match last_segment(ty) {
+        Some(seg) if seg.ident == "PinCell" || seg.ident == "PinMutex" => &seg.arguments,
+        _ => return false,
+    };
+    match inner {
+        PathArguments::AngleBracketed(args) => args.args.iter().any(|arg| match arg {
+            GenericArgument::Type(ty) => last_segment(ty).is_some_and(|seg| seg.ident == "GcStore"),
+            _ => false,
+        }),
+        _ => false,
+    }
+}
+
+fn last_segment(ty: &Type) -> Option<&PathSegment> {
+    match ty {
+        Type::Path(ty) => ty.path.segments.last(),
+        _ => None,
+    }
+}
+
+fn snake_case(ident: &str) -> String {
+    let mut snake = String::new();
+    for (i, ch) in ident.char_indices() {
+        if ch.is_uppercase() {
+            if i != 0 {
+                snake.push('_');
+            }
+            snake.extend(ch.to_lowercase());
+        } else {
+            snake.push(ch);
+        }
+    }
+    snake
+}
@@ -11,9 +11,13 @@ mod accessors;
 mod null_trace;
 mod reroot;
 mod trace;
+mod view;
+
+#[cfg(test)]
+mod tests;
 
 use proc_macro2::*;
-use syn::buffer::TokenBuffer;
+use quote::ToTokens;
 use syn::punctuated::Punctuated;
 use syn::*;
 
@@ -21,59 +25,354 @@ use crate::accessors::accessors;
 use crate::null_trace::null_trace_impl;
 use crate::reroot::reroot_impl;
 use crate::trace::trace_impl;
+use crate::view::view;
 
 decl_derive!([GC, attributes(gc)] => gc_derive);
 
 fn gc_derive(s: synstructure::Structure) -> TokenStream {
-    let tagged_fields = tagged_fields(&s);
-    let accessors = accessors(&s, &tagged_fields[..]);
-    let trace_impl = trace_impl(&s);
-    let reroot_impl = reroot_impl(&s);
-    let null_trace_impl = null_trace_impl(&s);
-    let gc_impl = gc_impl(&s);
-    quote! {
+    derive(&s).unwrap_or_else(|err| err.to_compile_error())
+}
+
+fn derive(s: &synstructure::Structure) -> Result<TokenStream> {
+    container_attrs(s)?;
+    check_field_attrs(s)?;
+
+    let accessors = accessors(s);
+    let view = view(s);
+    let trace_impl = trace_impl(s)?;
+    let reroot_impl = reroot_impl(s);
+    let null_trace_impl = null_trace_impl(s);
+    let gc_impl = gc_impl(s);
+    let rooted_impl = rooted_impl(s);
+    Ok(quote! {
         #accessors
+        #view
         #trace_impl
         #reroot_impl
         #null_trace_impl
         #gc_impl
-    }
+        #rooted_impl
+    })
 }
 
 fn gc_impl(s: &synstructure::Structure) -> TokenStream {
+    // Bounding the supertraits directly avoids recursing into the fields
+    let mut s = s.clone();
+    s.add_bounds(synstructure::AddBounds::None);
     s.gen_impl(quote! {
         extern crate elise;
 
-        gen impl<'__root> elise::GC<'__root> for @Self {
+        gen impl<'__root> elise::GC<'__root> for @Self where
+            Self: elise::raw::Reroot<'__root> + elise::raw::Trace,
+        {
         }
     })
 }
 
-fn tagged_fields<'a>(s: &'a synstructure::Structure<'a>) -> Vec<&'a synstructure::BindingInfo<'a>> {
-    s.variants()
-        .iter()
-        .flat_map(|v| v.bindings())
-        .filter(|binding| binding.ast().attrs.iter().any(|attr| is_attr(attr, "gc")))
-        .collect()
+/// Nested in a `#[gc]` field, the object is read rerooted, since its own
+/// `#[gc]` fields need a `Gc` to the object to be rooted
+fn rooted_impl(s: &synstructure::Structure) -> TokenStream {
+    let mut s = s.clone();
+    s.add_bounds(synstructure::AddBounds::None);
+    s.gen_impl(quote! {
+        extern crate elise;
+
+        gen unsafe impl<'__root> elise::raw::Rooted<'__root> for @Self where
+            Self: elise::raw::Reroot<'__root>,
+        {
+            type Rooted = <Self as elise::raw::Reroot<'__root>>::Rerooted;
+        }
+    })
 }
 
 fn is_attr(attr: &syn::Attribute, ident: &str) -> bool {
-    attr.path.segments.last().unwrap().value().ident == ident
+    attr.path().segments.last().unwrap().ident == ident
 }
 
-fn has_attr(s: &synstructure::Structure, ident: &str) -> bool {
-    if let Some(attr) = s.ast().attrs.iter().find(|attr| is_attr(attr, "gc")) {
-        let attr_content = attr.tts.clone().into_iter().next().unwrap();
-        if let TokenTree::Group(attr_content) = attr_content {
-            let buffer = TokenBuffer::new2(attr_content.stream());
-            let idents = Punctuated::<Ident, token::Comma>::parse_terminated(buffer.begin())
-                .unwrap()
-                .0;
-            idents.into_iter().any(|i| i == ident)
+/// Options accepted by `#[gc(...)]` on the type itself
+const OPTIONS: &[&str] = &["finalize", "unsafe_finalize", "null_trace", "view"];
+
+/// Everything given in `#[gc(...)]` on the type itself
+#[derive(Default)]
+struct ContainerAttrs {
+    options: Vec<Ident>,
+    bounds: Bounds,
+    root: Option<Lifetime>,
+}
+
+fn container_attrs(s: &synstructure::Structure) -> Result<ContainerAttrs> {
+    let mut attrs = ContainerAttrs::default();
+    for attr in s.ast().attrs.iter().filter(|attr| is_attr(attr, "gc")) {
+        let list = match &attr.meta {
+            Meta::List(list) => list,
+            meta => {
+                return Err(Error::new_spanned(
+                    meta,
+                    "expected `#[gc(...)]` with a list of options",
+                ))
+            }
+        };
+        list.parse_nested_meta(|meta| {
+            if meta.path.is_ident("bound") {
+                return parse_bound(&meta, &mut attrs.bounds);
+            }
+            if meta.path.is_ident("root") {
+                attrs.root = Some(meta.value()?.parse()?);
+                return Ok(());
+            }
+            match meta.path.get_ident() {
+                Some(ident) if OPTIONS.contains(&&*ident.to_string()) => {
+                    attrs.options.push(ident.clone());
+                    Ok(())
+                }
+                _ => Err(Error::new_spanned(
+                    &meta.path,
+                    format!("unknown gc option `{}`", meta.path.to_token_stream()),
+                )),
+            }
+        })?;
+    }
+
+    if let Some(root) = &attrs.root {
+        if !s
+            .ast()
+            .generics
+            .lifetimes()
+            .any(|param| param.lifetime == *root)
+        {
+            return Err(Error::new_spanned(
+                root,
+                format!("`{}` is not a lifetime parameter of this type", root),
+            ));
+        }
+    }
+    Ok(attrs)
+}
+
+/// The bounds of a type already validated by `derive`
+fn checked_container_bounds(s: &synstructure::Structure) -> Bounds {
+    container_attrs(s)
+        .map(|attrs| attrs.bounds)
+        .unwrap_or_default()
+}
+
+/// The lifetime rerooted by `Reroot`, `'root` unless set with `#[gc(root = 'a)]`
+fn root_lifetime(s: &synstructure::Structure) -> Lifetime {
+    container_attrs(s)
+        .ok()
+        .and_then(|attrs| attrs.root)
+        .unwrap_or_else(|| Lifetime::new("'root", Span::call_site()))
+}
+
+/// Where clauses replacing the ones inferred from the field types
+#[derive(Default)]
+struct Bounds {
+    trace: Option<Vec<WherePredicate>>,
+    reroot: Option<Vec<WherePredicate>>,
+    null_trace: Option<Vec<WherePredicate>>,
+}
+
+/// Parse `bound = "..."` or `bound(trace = "...", reroot = "...", null_trace = "...")`
+fn parse_bound(meta: &meta::ParseNestedMeta, bounds: &mut Bounds) -> Result<()> {
+    if meta.input.peek(Token![=]) {
+        let predicates = parse_predicates(&meta.value()?.parse()?)?;
+        bounds.trace = Some(predicates.clone());
+        bounds.reroot = Some(predicates.clone());
+        bounds.null_trace = Some(predicates);
+        return Ok(());
+    }
+    meta.parse_nested_meta(|meta| {
+        let bound = if meta.path.is_ident("trace") {
+            &mut bounds.trace
+        } else if meta.path.is_ident("reroot") {
+            &mut bounds.reroot
+        } else if meta.path.is_ident("null_trace") {
+            &mut bounds.null_trace
         } else {
-            false
+            return Err(meta.error("expected `trace`, `reroot` or `null_trace`"));
+        };
+        *bound = Some(parse_predicates(&meta.value()?.parse()?)?);
+        Ok(())
+    })
+}
+
+fn parse_predicates(lit: &LitStr) -> Result<Vec<WherePredicate>> {
+    let predicates = lit.parse_with(Punctuated::<WherePredicate, Token![,]>::parse_terminated)?;
+    Ok(predicates.into_iter().collect())
+}
+
+fn check_field_attrs(s: &synstructure::Structure) -> Result<()> {
+    let is_enum = matches!(s.ast().data, Data::Enum(_));
+    for variant in s.variants() {
+        let mut attrs = variant.ast().attrs.iter().filter(|_| is_enum);
+        if let Some(attr) = attrs.find(|attr| is_attr(attr, "gc")) {
+            return Err(Error::new_spanned(
+                attr,
+                "`#[gc]` must be put on the fields of a variant, not the variant itself",
+            ));
+        }
+        for binding in variant.bindings() {
+            field_attr(binding.ast())?;
         }
-    } else {
-        false
     }
+    Ok(())
+}
+
+/// How a field is handled, from its `#[gc]` attribute
+enum FieldAttr {
+    /// No attribute: the field is traced
+    None,
+    /// `#[gc]`: the field is traced and gets an accessor
+    Accessor,
+    /// `#[gc(skip)]`: the field is `'static` and never traced
+    Skip,
+    /// `#[gc(unsafe_trace_with = path, finalize_with = path)]`: the field is
+    /// traced and finalized by calling these functions
+    TraceWith { trace: Path, finalize: Path },
+}
+
+fn field_attr(field: &Field) -> Result<FieldAttr> {
+    parse_field_attrs(field).map(|(field_attr, _)| field_attr)
+}
+
+fn parse_field_attrs(field: &Field) -> Result<(FieldAttr, Bounds)> {
+    let mut field_attr = FieldAttr::None;
+    let mut bounds = Bounds::default();
+    let mut trace_with: Option<(Path, Path)> = None;
+    let mut finalize_with: Option<(Path, Path)> = None;
+    for attr in field.attrs.iter().filter(|attr| is_attr(attr, "gc")) {
+        let list = match &attr.meta {
+            Meta::Path(_) => {
+                set_field_attr(&mut field_attr, FieldAttr::Accessor, attr)?;
+                continue;
+            }
+            Meta::List(list) => list,
+            meta => {
+                return Err(Error::new_spanned(
+                    meta,
+                    "expected `#[gc]`, `#[gc(skip)]` or `#[gc(unsafe_trace_with = path)]`",
+                ))
+            }
+        };
+        if list.tokens.is_empty() {
+            return Err(Error::new_spanned(
+                list,
+                "expected `skip`, `unsafe_trace_with`, `finalize_with` or `bound`",
+            ));
+        }
+        list.parse_nested_meta(|meta| {
+            if meta.path.is_ident("skip") {
+                set_field_attr(&mut field_attr, FieldAttr::Skip, &meta.path)
+            } else if meta.path.is_ident("unsafe_trace_with") {
+                set_field_path(&mut trace_with, &meta)
+            } else if meta.path.is_ident("finalize_with") {
+                set_field_path(&mut finalize_with, &meta)
+            } else if meta.path.is_ident("bound") {
+                parse_bound(&meta, &mut bounds)
+            } else {
+                Err(meta.error(
+                    "unknown field option, expected `skip`, `unsafe_trace_with`, \
+                     `finalize_with` or `bound`",
+                ))
+            }
+        })?;
+    }
+    // Dropping a field which contains GC pointers would read objects the
+    // sweep may already have freed, so it must be finalized by the user too
+    match (trace_with, finalize_with) {
+        (Some((option, trace)), Some((_, finalize))) => set_field_attr(
+            &mut field_attr,
+            FieldAttr::TraceWith { trace, finalize },
+            option,
+        )?,
+        (Some((option, _)), None) => {
+            return Err(Error::new_spanned(
+                option,
+                "`unsafe_trace_with` fields must also have a `finalize_with` function",
+            ))
+        }
+        (None, Some((option, _))) => {
+            return Err(Error::new_spanned(
+                option,
+                "`finalize_with` can only be used with `unsafe_trace_with`",
+            ))
+        }
+        (None, None) => {}
+    }
+    Ok((field_attr, bounds))
+}
+
+/// Parse `option = path`, keeping the option to point errors at
+fn set_field_path(path: &mut Option<(Path, Path)>, meta: &meta::ParseNestedMeta) -> Result<()> {
+    if path.is_some() {
+        return Err(meta.error(format!(
+            "duplicate `{}` option",
+            meta.path.to_token_stream()
+        )));
+    }
+    *path = Some((meta.path.clone(), meta.value()?.parse()?));
+    Ok(())
+}
+
+fn set_field_attr(field_attr: &mut FieldAttr, new: FieldAttr, tokens: impl ToTokens) -> Result<()> {
+    if !matches!(field_attr, FieldAttr::None) {
+        return Err(Error::new_spanned(
+            tokens,
+            "a field can only have one of `#[gc]`, `#[gc(skip)]` and `#[gc(unsafe_trace_with)]`",
+        ));
+    }
+    *field_attr = new;
+    Ok(())
+}
+
+/// The attribute of a field already validated by `check_field_attrs`
+fn checked_field_attr(field: &Field) -> FieldAttr {
+    field_attr(field).unwrap_or(FieldAttr::None)
+}
+
+/// The bounds of a field already validated by `check_field_attrs`
+fn checked_field_bounds(field: &Field) -> Bounds {
+    parse_field_attrs(field)
+        .map(|(_, bounds)| bounds)
+        .unwrap_or_default()
+}
+
+/// Apply the `bound` attributes for one of the generated impls
+///
+/// Returns the structure to generate the impl from, without the fields whose
+/// bounds were replaced, and the bounds the user gave instead.
+fn user_bounds<'a>(
+    s: &synstructure::Structure<'a>,
+    select: impl Fn(Bounds) -> Option<Vec<WherePredicate>>,
+) -> (synstructure::Structure<'a>, Vec<WherePredicate>) {
+    let mut s = s.clone();
+    if let Some(bound) = select(checked_container_bounds(&s)) {
+        s.filter(|_| false);
+        s.add_bounds(synstructure::AddBounds::None);
+        return (s, bound);
+    }
+    let mut bounds = vec![];
+    s.filter(|b| match select(checked_field_bounds(b.ast())) {
+        Some(bound) => {
+            bounds.extend(bound);
+            false
+        }
+        None => true,
+    });
+    (s, bounds)
+}
+
+/// Leave the fields which are not traced out of the bounds synstructure
+/// infers, since their types need not implement any of the traits
+fn skip_untraced_bounds(s: &mut synstructure::Structure) {
+    s.filter(|b| {
+        !matches!(
+            checked_field_attr(b.ast()),
+            FieldAttr::Skip | FieldAttr::TraceWith { .. }
+        )
+    });
+}
+
+fn has_attr(s: &synstructure::Structure, ident: &str) -> bool {
+    container_attrs(s).is_ok_and(|attrs| attrs.options.iter().any(|i| i == ident))
 }
@@ -1,15 +1,22 @@
 use proc_macro2::*;
 use synstructure::*;
 
+use crate::FieldAttr;
+
 pub fn null_trace_impl(s: &Structure) -> TokenStream {
     if !super::has_attr(s, "null_trace") {
         return quote!();
     }
 
+    // A type with a GC pointer in it must never be `NullTrace`, or it could be
+    // moved out of a `Cell` without being traced, so user bounds are added to
+    // the field bounds instead of replacing them
+    let (_, user_bounds) = super::user_bounds(s, |bounds| bounds.null_trace);
     let bounds = s
         .variants()
-        .into_iter()
+        .iter()
         .flat_map(|v| v.bindings())
+        .filter(|b| !matches!(super::checked_field_attr(b.ast()), FieldAttr::Skip))
         .map(|b| {
             let ty = &b.ast().ty;
             quote! {
@@ -17,10 +24,13 @@ pub fn null_trace_impl(s: &Structure) -> TokenStream {
             }
         });
 
+    let mut s = s.clone();
+    s.add_bounds(AddBounds::None);
     s.gen_impl(quote! {
         extern crate elise;
 
         gen unsafe impl elise::raw::NullTrace for @Self where
+            #(#user_bounds,)*
             #(#bounds,)*
         { }
     })
@@ -2,15 +2,33 @@ use proc_macro2::*;
 use syn::*;
 use synstructure::*;
 
+use crate::FieldAttr;
+
 pub fn reroot_impl(s: &Structure) -> TokenStream {
-    let rerooted = rerooted(s);
+    let mut folder = RootFolder {
+        root: super::root_lifetime(s),
+    };
+    let rerooted = fold::fold_path_segment(&mut folder, self_type(s));
+
+    // The rerooted type must outlive the new root, and so must any borrows
+    let root = folder.root.clone();
+    let lifetimes = s
+        .ast()
+        .generics
+        .lifetimes()
+        .map(|param| &param.lifetime)
+        .filter(|lifetime| **lifetime != root);
 
-    let bounds = bounds(s);
+    let (mut bounded, user_bounds) = super::user_bounds(s, |bounds| bounds.reroot);
+    let bounds = bounds(&bounded, &mut folder);
+    super::skip_untraced_bounds(&mut bounded);
 
-    s.gen_impl(quote! {
+    bounded.gen_impl(quote! {
         extern crate elise;
 
         gen unsafe impl<'__root> elise::raw::Reroot<'__root> for @Self where
+            #(#lifetimes: '__root,)*
+            #(#user_bounds,)*
             #(#bounds,)*
         {
             type Rerooted = #rerooted;
@@ -18,25 +36,32 @@ pub fn reroot_impl(s: &Structure) -> TokenStream {
     })
 }
 
-fn bounds<'a>(s: &'a Structure) -> impl Iterator<Item = TokenStream> + 'a {
+fn bounds(s: &Structure, folder: &mut RootFolder) -> Vec<TokenStream> {
     s.variants()
-        .into_iter()
+        .iter()
         .flat_map(|variant| variant.bindings())
-        .map(|b| field_where_clause(b.ast()))
+        .map(|b| field_where_clause(b.ast(), folder))
+        .collect()
 }
 
-fn field_where_clause(field: &Field) -> TokenStream {
+fn field_where_clause(field: &Field, folder: &mut RootFolder) -> TokenStream {
     let ty = &field.ty;
-    let rerooted_ty = fold::fold_type(&mut RootFolder, ty.clone());
-    quote! {
-        #ty: elise::raw::Reroot<'__root, Rerooted = #rerooted_ty>
+    let rerooted_ty = fold::fold_type(folder, ty.clone());
+    match super::checked_field_attr(field) {
+        FieldAttr::Skip => quote! {
+            #ty: 'static
+        },
+        // Foreign types can't implement Reroot, so trust the user that
+        // replacing the root lifetime is enough to reroot them
+        FieldAttr::TraceWith { .. } => quote! {
+            #rerooted_ty: '__root
+        },
+        _ => quote! {
+            #ty: elise::raw::Reroot<'__root, Rerooted = #rerooted_ty>
+        },
     }
 }
 
-fn rerooted(s: &Structure) -> PathSegment {
-    fold::fold_path_segment(&mut RootFolder, self_type(s))
-}
-
 fn self_type(s: &Structure) -> PathSegment {
     let ident = s.ast().ident.clone();
     let args = s
@@ -62,17 +87,20 @@ fn self_type(s: &Structure) -> PathSegment {
         arguments: PathArguments::AngleBracketed(AngleBracketedGenericArguments {
             args,
             colon2_token: None,
-            lt_token: token::Lt::new(Span::call_site()),
-            gt_token: token::Gt::new(Span::call_site()),
+            lt_token: Default::default(),
+            gt_token: Default::default(),
         }),
     }
 }
 
-pub struct RootFolder;
+/// Replaces the root lifetime with `'__root`
+pub struct RootFolder {
+    root: Lifetime,
+}
 
 impl fold::Fold for RootFolder {
     fn fold_lifetime(&mut self, lifetime: Lifetime) -> Lifetime {
-        if lifetime.ident == "root" {
+        if lifetime == self.root {
             Lifetime::new("'__root", Span::call_site())
         } else {
             lifetime
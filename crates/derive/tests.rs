@@ -0,0 +1,707 @@
+use super::gc_derive;
+
+#[test]
+fn accessors() {
+    test_derive! {
+        gc_derive {
+            struct Foo<'root> {
+                #[gc]
+                bar: GcStore<'root, i32>,
+                baz: i32,
+            }
+        }
+        expands to {
+            impl<'root> Foo<'root> {
+                fn bar<'__root>(
+                    self: &'__root elise::Gc<'__root, Self>
+                ) -> <GcStore<'root, i32> as elise::raw::Store<'__root>>::Accessor {
+                    unsafe { elise::raw::Store::rooted(&self.bar) }
+                }
+            }
+            const _: () = {
+                extern crate elise;
+                unsafe impl<'root> elise::raw::Trace for Foo<'root> {
+                    unsafe fn trace(&self, tracer: &mut dyn elise::raw::Tracer) {
+                        match self {
+                            Foo {
+                                bar: ref __binding_0,
+                                baz: ref __binding_1,
+                            } => {
+                                {
+                                    elise::raw::trace_field(tracer, "bar", __binding_0)
+                                }
+                                {
+                                    elise::raw::trace_field(tracer, "baz", __binding_1)
+                                }
+                            }
+                        }
+                    }
+                    unsafe fn finalize(&mut self) {
+                        match self {
+                            Foo {
+                                bar: ref mut __binding_0,
+                                baz: ref mut __binding_1,
+                            } => {
+                                {
+                                    __binding_0.finalize()
+                                }
+                                {
+                                    __binding_1.finalize()
+                                }
+                            }
+                        }
+                    }
+                }
+            };
+            const _: () = {
+                extern crate elise;
+                unsafe impl<'__root, 'root> elise::raw::Reroot<'__root> for Foo<'root>
+                where
+                    GcStore<'root, i32>: elise::raw::Reroot<'__root, Rerooted = GcStore<'__root, i32> >,
+                    i32: elise::raw::Reroot<'__root, Rerooted = i32>,
+                {
+                    type Rerooted = Foo<'__root>;
+                }
+            };
+            const _: () = {
+                extern crate elise;
+                impl<'__root, 'root> elise::GC<'__root> for Foo<'root>
+                where
+                    Self: elise::raw::Reroot<'__root> + elise::raw::Trace,
+                {
+                }
+            };
+            const _: () = {
+                extern crate elise;
+                unsafe impl<'__root, 'root> elise::raw::Rooted<'__root> for Foo<'root>
+                where
+                    Self: elise::raw::Reroot<'__root>,
+                {
+                    type Rooted = <Self as elise::raw::Reroot<'__root>>::Rerooted;
+                }
+            };
+        }
+        no_build
+    }
+}
+
+#[test]
+fn null_trace() {
+    test_derive! {
+        gc_derive {
+            #[gc(null_trace)]
+            enum Null {
+                A(i32),
+                B(String),
+            }
+        }
+        expands to {
+            impl Null {}
+            const _: () = {
+                extern crate elise;
+                unsafe impl elise::raw::Trace for Null {
+                    unsafe fn trace(&self, tracer: &mut dyn elise::raw::Tracer) {
+                        match self {
+                            Null::A(ref __binding_0,) => {
+                            { __binding_0.trace(tracer) }
+                        }
+                            Null::B(ref __binding_0,) => {
+                            { __binding_0.trace(tracer) }
+                        }
+                        }
+                    }
+                    unsafe fn finalize(&mut self) {
+                        match self {
+                            Null::A(ref mut __binding_0,) => {
+                            { __binding_0.finalize() }
+                        }
+                            Null::B(ref mut __binding_0,) => {
+                            { __binding_0.finalize() }
+                        }
+                        }
+                    }
+                }
+            };
+            const _: () = {
+                extern crate elise;
+                unsafe impl<'__root> elise::raw::Reroot<'__root> for Null
+                where
+                    i32: elise::raw::Reroot<'__root, Rerooted = i32>,
+                    String: elise::raw::Reroot<'__root, Rerooted = String>,
+                {
+                    type Rerooted = Null<>;
+                }
+            };
+            const _: () = {
+                extern crate elise;
+                unsafe impl elise::raw::NullTrace for Null
+                where
+                    i32: elise::raw::NullTrace,
+                    String: elise::raw::NullTrace,
+                {
+                }
+            };
+            const _: () = {
+                extern crate elise;
+                impl<'__root> elise::GC<'__root> for Null
+                where
+                    Self: elise::raw::Reroot<'__root> + elise::raw::Trace,
+                {
+                }
+            };
+            const _: () = {
+                extern crate elise;
+                unsafe impl<'__root> elise::raw::Rooted<'__root> for Null
+                where
+                    Self: elise::raw::Reroot<'__root>,
+                {
+                    type Rooted = <Self as elise::raw::Reroot<'__root>>::Rerooted;
+                }
+            };
+        }
+        no_build
+    }
+}
+
+#[test]
+fn field_options() {
+    test_derive! {
+        gc_derive {
+            struct Foo {
+                #[gc(skip)]
+                name: String,
+                #[gc(unsafe_trace_with = trace_handle, finalize_with = finalize_handle)]
+                handle: Handle,
+            }
+        }
+        expands to {
+            impl Foo {}
+            const _: () = {
+                extern crate elise;
+                unsafe impl elise::raw::Trace for Foo
+                where
+                    String: 'static,
+                {
+                    unsafe fn trace(&self, tracer: &mut dyn elise::raw::Tracer) {
+                        match self {
+                            Foo {
+                                handle: ref __binding_1,
+                                ..
+                            } => {
+                                {
+                                    tracer.enter_field("handle");
+                                    trace_handle(__binding_1, tracer);
+                                    tracer.exit_field();
+                                }
+                            }
+                        }
+                    }
+                    unsafe fn finalize(&mut self) {
+                        match self {
+                            Foo {
+                                name: ref mut __binding_0,
+                                handle: ref mut __binding_1,
+                            } => {
+                                {
+                                    std::ptr::drop_in_place(__binding_0)
+                                }
+                                {
+                                    finalize_handle(__binding_1)
+                                }
+                            }
+                        }
+                    }
+                }
+            };
+            const _: () = {
+                extern crate elise;
+                unsafe impl<'__root> elise::raw::Reroot<'__root> for Foo
+                where
+                    String: 'static,
+                    Handle: '__root,
+                {
+                    type Rerooted = Foo<>;
+                }
+            };
+            const _: () = {
+                extern crate elise;
+                impl<'__root> elise::GC<'__root> for Foo
+                where
+                    Self: elise::raw::Reroot<'__root> + elise::raw::Trace,
+                {
+                }
+            };
+            const _: () = {
+                extern crate elise;
+                unsafe impl<'__root> elise::raw::Rooted<'__root> for Foo
+                where
+                    Self: elise::raw::Reroot<'__root>,
+                {
+                    type Rooted = <Self as elise::raw::Reroot<'__root>>::Rerooted;
+                }
+            };
+        }
+        no_build
+    }
+}
+
+#[test]
+fn untraced_generics() {
+    test_derive! {
+        gc_derive {
+            #[gc(null_trace)]
+            struct Foo<T> {
+                #[gc(skip)]
+                foreign: Foreign<T>,
+                #[gc(unsafe_trace_with = trace_handle, finalize_with = finalize_handle)]
+                handle: Handle<T>,
+            }
+        }
+        expands to {
+            impl<T> Foo<T> {}
+            const _: () = {
+                extern crate elise;
+                unsafe impl<T> elise::raw::Trace for Foo<T>
+                where
+                    Foreign<T>: 'static,
+                {
+                    unsafe fn trace(&self, tracer: &mut dyn elise::raw::Tracer) {
+                        match self {
+                            Foo {
+                                handle: ref __binding_1,
+                                ..
+                            } => {
+                                {
+                                    tracer.enter_field("handle");
+                                    trace_handle(__binding_1, tracer);
+                                    tracer.exit_field();
+                                }
+                            }
+                        }
+                    }
+                    unsafe fn finalize(&mut self) {
+                        match self {
+                            Foo {
+                                foreign: ref mut __binding_0,
+                                handle: ref mut __binding_1,
+                            } => {
+                                {
+                                    std::ptr::drop_in_place(__binding_0)
+                                }
+                                {
+                                    finalize_handle(__binding_1)
+                                }
+                            }
+                        }
+                    }
+                }
+            };
+            const _: () = {
+                extern crate elise;
+                unsafe impl<'__root, T> elise::raw::Reroot<'__root> for Foo<T>
+                where
+                    Foreign<T>: 'static,
+                    Handle<T>: '__root,
+                {
+                    type Rerooted = Foo<T>;
+                }
+            };
+            const _: () = {
+                extern crate elise;
+                unsafe impl<T> elise::raw::NullTrace for Foo<T>
+                where
+                    Handle<T>: elise::raw::NullTrace,
+                {
+                }
+            };
+            const _: () = {
+                extern crate elise;
+                impl<'__root, T> elise::GC<'__root> for Foo<T>
+                where
+                    Self: elise::raw::Reroot<'__root> + elise::raw::Trace,
+                {
+                }
+            };
+            const _: () = {
+                extern crate elise;
+                unsafe impl<'__root, T> elise::raw::Rooted<'__root> for Foo<T>
+                where
+                    Self: elise::raw::Reroot<'__root>,
+                {
+                    type Rooted = <Self as elise::raw::Reroot<'__root>>::Rerooted;
+                }
+            };
+        }
+        no_build
+    }
+}
+
+#[test]
+fn bounds() {
+    test_derive! {
+        gc_derive {
+            #[gc(bound(reroot = "T: Reroot<'__root, Rerooted = T>"))]
+            struct Foo<T> {
+                value: Wrapper<T>,
+            }
+        }
+        expands to {
+            impl<T> Foo<T> {}
+            const _: () = {
+                extern crate elise;
+                unsafe impl<T> elise::raw::Trace for Foo<T>
+                where
+                    Wrapper<T>: elise::raw::Trace,
+                    T: elise::raw::Trace
+                {
+                    unsafe fn trace(&self, tracer: &mut dyn elise::raw::Tracer) {
+                        match self {
+                            Foo {
+                                value: ref __binding_0,
+                            } => {
+                                {
+                                    elise::raw::trace_field(tracer, "value", __binding_0)
+                                }
+                            }
+                        }
+                    }
+                    unsafe fn finalize(&mut self) {
+                        match self {
+                            Foo {
+                                value: ref mut __binding_0,
+                            } => {
+                                {
+                                    __binding_0.finalize()
+                                }
+                            }
+                        }
+                    }
+                }
+            };
+            const _: () = {
+                extern crate elise;
+                unsafe impl<'__root, T> elise::raw::Reroot<'__root> for Foo<T>
+                where
+                    T: Reroot<'__root, Rerooted = T>,
+                {
+                    type Rerooted = Foo<T>;
+                }
+            };
+            const _: () = {
+                extern crate elise;
+                impl<'__root, T> elise::GC<'__root> for Foo<T>
+                where
+                    Self: elise::raw::Reroot<'__root> + elise::raw::Trace,
+                {
+                }
+            };
+            const _: () = {
+                extern crate elise;
+                unsafe impl<'__root, T> elise::raw::Rooted<'__root> for Foo<T>
+                where
+                    Self: elise::raw::Reroot<'__root>,
+                {
+                    type Rooted = <Self as elise::raw::Reroot<'__root>>::Rerooted;
+                }
+            };
+        }
+        no_build
+    }
+}
+
+#[test]
+fn enum_accessors() {
+    test_derive! {
+        gc_derive {
+            enum Value<'root> {
+                Pair(#[gc] GcStore<'root, i32>, i32),
+                Typed {
+                    #[gc]
+                    r#type: GcStore<'root, String>,
+                },
+            }
+        }
+        expands to {
+            impl<'root> Value<'root> {
+                #[allow(unused_variables)]
+                fn pair_0<'__root>(
+                    self: &'__root elise::Gc<'__root, Self>
+                ) -> Option<<GcStore<'root, i32> as elise::raw::Store<'__root>>::Accessor> {
+                    match **self {
+                        Value::Pair(ref __binding_0, ref __binding_1,) => Some(unsafe { elise::raw::Store::rooted(__binding_0) }),
+                        #[allow(unreachable_patterns)]
+                        _ => None,
+                    }
+                }
+                #[allow(unused_variables)]
+                fn typed_type<'__root>(
+                    self: &'__root elise::Gc<'__root, Self>
+                ) -> Option<<GcStore<'root, String> as elise::raw::Store<'__root>>::Accessor> {
+                    match **self {
+                        Value::Typed { r#type: ref __binding_0, } => Some(unsafe { elise::raw::Store::rooted(__binding_0) }),
+                        #[allow(unreachable_patterns)]
+                        _ => None,
+                    }
+                }
+            }
+            const _: () = {
+                extern crate elise;
+                unsafe impl<'root> elise::raw::Trace for Value<'root> {
+                    unsafe fn trace(&self, tracer: &mut dyn elise::raw::Tracer) {
+                        match self {
+                            Value::Pair(ref __binding_0, ref __binding_1,) => {
+                                {
+                                    __binding_0.trace(tracer)
+                                }
+                                {
+                                    __binding_1.trace(tracer)
+                                }
+                            }
+                            Value::Typed {
+                                r#type: ref __binding_0,
+                            } => {
+                                {
+                                    elise::raw::trace_field(tracer, "r#type", __binding_0)
+                                }
+                            }
+                        }
+                    }
+                    unsafe fn finalize(&mut self) {
+                        match self {
+                            Value::Pair(ref mut __binding_0, ref mut __binding_1,) => {
+                                {
+                                    __binding_0.finalize()
+                                }
+                                {
+                                    __binding_1.finalize()
+                                }
+                            }
+                            Value::Typed {
+                                r#type: ref mut __binding_0,
+                            } => {
+                                {
+                                    __binding_0.finalize()
+                                }
+                            }
+                        }
+                    }
+                }
+            };
+            const _: () = {
+                extern crate elise;
+                unsafe impl<'__root, 'root> elise::raw::Reroot<'__root> for Value<'root>
+                where
+                    GcStore<'root, i32>: elise::raw::Reroot<'__root, Rerooted = GcStore<'__root, i32> >,
+                    i32: elise::raw::Reroot<'__root, Rerooted = i32>,
+                    GcStore<'root, String>: elise::raw::Reroot<'__root, Rerooted = GcStore<'__root, String> >,
+                {
+                    type Rerooted = Value<'__root>;
+                }
+            };
+            const _: () = {
+                extern crate elise;
+                impl<'__root, 'root> elise::GC<'__root> for Value<'root>
+                where
+                    Self: elise::raw::Reroot<'__root> + elise::raw::Trace,
+                {
+                }
+            };
+            const _: () = {
+                extern crate elise;
+                unsafe impl<'__root, 'root> elise::raw::Rooted<'__root> for Value<'root>
+                where
+                    Self: elise::raw::Reroot<'__root>,
+                {
+                    type Rooted = <Self as elise::raw::Reroot<'__root>>::Rerooted;
+                }
+            };
+        }
+        no_build
+    }
+}
+
+#[test]
+fn tuple_struct_accessors() {
+    test_derive! {
+        gc_derive {
+            struct Pair<'root>(#[gc] PinCell<GcStore<'root, i32>>, i32);
+        }
+        expands to {
+            impl<'root> Pair<'root> {
+                fn _0<'__root>(
+                    self: &'__root elise::Gc<'__root, Self>
+                ) -> <PinCell<GcStore<'root, i32> > as elise::raw::Store<'__root>>::Accessor {
+                    unsafe { elise::raw::Store::rooted(&self.0) }
+                }
+                fn set_0<'__root>(
+                    self: elise::Gc<'__root, Self>,
+                    value: elise::Gc<'__root, <PinCell<GcStore<'root, i32> > as elise::raw::StoreCell<'__root>>::Target>
+                ) {
+                    unsafe {
+                        elise::raw::StoreCell::replace(&self.0, value);
+                    }
+                }
+                fn replace_0<'__root, '__new>(
+                    self: elise::Gc<'__root, Self>,
+                    value: elise::Gc<'__root, <PinCell<GcStore<'root, i32> > as elise::raw::StoreCell<'__root>>::Target>,
+                    root: elise::Root<'__new>,
+                ) -> elise::Gc<'__new, < <PinCell<GcStore<'root, i32> > as elise::raw::StoreCell<'__root>>::Target as elise::raw::Reroot<'__new>>::Rerooted> {
+                    unsafe {
+                        let old = elise::raw::StoreCell::replace(&self.0, value);
+                        root.reroot(elise::Gc::rooted(old))
+                    }
+                }
+            }
+            const _: () = {
+                extern crate elise;
+                unsafe impl<'root> elise::raw::Trace for Pair<'root> {
+                    unsafe fn trace(&self, tracer: &mut dyn elise::raw::Tracer) {
+                        match self {
+                            Pair(ref __binding_0, ref __binding_1,) => {
+                                {
+                                    __binding_0.trace(tracer)
+                                }
+                                {
+                                    __binding_1.trace(tracer)
+                                }
+                            }
+                        }
+                    }
+                    unsafe fn finalize(&mut self) {
+                        match self {
+                            Pair(ref mut __binding_0, ref mut __binding_1,) => {
+                                {
+                                    __binding_0.finalize()
+                                }
+                                {
+                                    __binding_1.finalize()
+                                }
+                            }
+                        }
+                    }
+                }
+            };
+            const _: () = {
+                extern crate elise;
+                unsafe impl<'__root, 'root> elise::raw::Reroot<'__root> for Pair<'root>
+                where
+                    PinCell<GcStore<'root, i32> >: elise::raw::Reroot<'__root, Rerooted = PinCell<GcStore<'__root, i32> > >,
+                    i32: elise::raw::Reroot<'__root, Rerooted = i32>,
+                {
+                    type Rerooted = Pair<'__root>;
+                }
+            };
+            const _: () = {
+                extern crate elise;
+                impl<'__root, 'root> elise::GC<'__root> for Pair<'root>
+                where
+                    Self: elise::raw::Reroot<'__root> + elise::raw::Trace,
+                {
+                }
+            };
+            const _: () = {
+                extern crate elise;
+                unsafe impl<'__root, 'root> elise::raw::Rooted<'__root> for Pair<'root>
+                where
+                    Self: elise::raw::Reroot<'__root>,
+                {
+                    type Rooted = <Self as elise::raw::Reroot<'__root>>::Rerooted;
+                }
+            };
+        }
+        no_build
+    }
+}
+
+#[test]
+fn raw_identifier_setters() {
+    test_derive! {
+        gc_derive {
+            struct Typed<'root> {
+                #[gc]
+                r#type: PinCell<GcStore<'root, String>>,
+            }
+        }
+        expands to {
+            impl<'root> Typed<'root> {
+                fn r#type<'__root>(
+                    self: &'__root elise::Gc<'__root, Self>
+                ) -> <PinCell<GcStore<'root, String> > as elise::raw::Store<'__root>>::Accessor {
+                    unsafe { elise::raw::Store::rooted(&self.r#type) }
+                }
+                fn set_type<'__root>(
+                    self: elise::Gc<'__root, Self>,
+                    value: elise::Gc<'__root, <PinCell<GcStore<'root, String> > as elise::raw::StoreCell<'__root>>::Target>
+                ) {
+                    unsafe {
+                        elise::raw::StoreCell::replace(&self.r#type, value);
+                    }
+                }
+                fn replace_type<'__root, '__new>(
+                    self: elise::Gc<'__root, Self>,
+                    value: elise::Gc<'__root, <PinCell<GcStore<'root, String> > as elise::raw::StoreCell<'__root>>::Target>,
+                    root: elise::Root<'__new>,
+                ) -> elise::Gc<'__new, < <PinCell<GcStore<'root, String> > as elise::raw::StoreCell<'__root>>::Target as elise::raw::Reroot<'__new>>::Rerooted> {
+                    unsafe {
+                        let old = elise::raw::StoreCell::replace(&self.r#type, value);
+                        root.reroot(elise::Gc::rooted(old))
+                    }
+                }
+            }
+            const _: () = {
+                extern crate elise;
+                unsafe impl<'root> elise::raw::Trace for Typed<'root> {
+                    unsafe fn trace(&self, tracer: &mut dyn elise::raw::Tracer) {
+                        match self {
+                            Typed {
+                                r#type: ref __binding_0,
+                            } => {
+                                {
+                                    elise::raw::trace_field(tracer, "r#type", __binding_0)
+                                }
+                            }
+                        }
+                    }
+                    unsafe fn finalize(&mut self) {
+                        match self {
+                            Typed {
+                                r#type: ref mut __binding_0,
+                            } => {
+                                {
+                                    __binding_0.finalize()
+                                }
+                            }
+                        }
+                    }
+                }
+            };
+            const _: () = {
+                extern crate elise;
+                unsafe impl<'__root, 'root> elise::raw::Reroot<'__root> for Typed<'root>
+                where
+                    PinCell<GcStore<'root, String> >: elise::raw::Reroot<'__root, Rerooted = PinCell<GcStore<'__root, String> > >,
+                {
+                    type Rerooted = Typed<'__root>;
+                }
+            };
+            const _: () = {
+                extern crate elise;
+                impl<'__root, 'root> elise::GC<'__root> for Typed<'root>
+                where
+                    Self: elise::raw::Reroot<'__root> + elise::raw::Trace,
+                {
+                }
+            };
+            const _: () = {
+                extern crate elise;
+                unsafe impl<'__root, 'root> elise::raw::Rooted<'__root> for Typed<'root>
+                where
+                    Self: elise::raw::Reroot<'__root>,
+                {
+                    type Rooted = <Self as elise::raw::Reroot<'__root>>::Rerooted;
+                }
+            };
+        }
+        no_build
+    }
+}
@@ -2,76 +2,116 @@ use proc_macro2::*;
 use syn::*;
 use synstructure::*;
 
+use crate::FieldAttr;
+
 enum HasDrop {
     None,
     Drop,
     UnsafeDrop,
 }
 
-pub fn trace_impl(s: &Structure) -> TokenStream {
-    let mark_body = s.each(|b| quote!(#b.mark()));
-    let manage_body = s.each(|b| quote!(#b.manage()));
-    let finalize_body = s
+pub fn trace_impl(s: &Structure) -> Result<TokenStream> {
+    let trace_body = s
         .clone()
-        .bind_with(|_| BindStyle::RefMut)
-        .each(|b| quote!(#b.finalize()));
-    let drop = has_drop(s);
+        .filter(|b| !matches!(super::checked_field_attr(b.ast()), FieldAttr::Skip))
+        .each(|b| {
+            let name = b.ast().ident.as_ref().map(|ident| ident.to_string());
+            match (super::checked_field_attr(b.ast()), name) {
+                (FieldAttr::TraceWith { trace, .. }, Some(name)) => quote! {
+                    tracer.enter_field(#name);
+                    #trace(#b, tracer);
+                    tracer.exit_field();
+                },
+                (FieldAttr::TraceWith { trace, .. }, None) => quote!(#trace(#b, tracer)),
+                (_, Some(name)) => quote!(elise::raw::trace_field(tracer, #name, #b)),
+                (_, None) => quote!(#b.trace(tracer)),
+            }
+        });
+    let finalize_body = s.clone().bind_with(|_| BindStyle::RefMut).each(|b| {
+        match super::checked_field_attr(b.ast()) {
+            // Skipped fields have no GC pointers and may not implement
+            // Trace, so just drop them
+            FieldAttr::Skip => quote!(std::ptr::drop_in_place(#b)),
+            FieldAttr::TraceWith { finalize, .. } => quote!(#finalize(#b)),
+            _ => quote!(#b.finalize()),
+        }
+    });
+    // Skipped fields must not contain any GC pointers
+    let skipped = s
+        .variants()
+        .iter()
+        .flat_map(|v| v.bindings())
+        .filter(|b| matches!(super::checked_field_attr(b.ast()), FieldAttr::Skip))
+        .map(|b| {
+            let ty = &b.ast().ty;
+            quote!(#ty: 'static)
+        });
+    let drop = has_drop(s)?;
     let drop_glue = match &drop {
         HasDrop::None => quote!(),
         _ => quote!(elise::Finalize::finalize(self)),
     };
     let bound = match &drop {
         HasDrop::Drop => {
-            assert!(
-                only_has_root_lifetime(s),
-                "GC'd objects with lifetimes other than 'root must use UnsafeFinalize"
-            );
-            quote! { for<'__root> Self: elise::raw::Reroot<'__root> }
+            only_has_root_lifetime(s)?;
+            quote! { for<'__root> Self: elise::raw::Reroot<'__root>, }
         }
         _ => quote! {},
     };
-    s.gen_impl(quote! {
+    let (mut bounded, user_bounds) = super::user_bounds(s, |bounds| bounds.trace);
+    super::skip_untraced_bounds(&mut bounded);
+    Ok(bounded.gen_impl(quote! {
         extern crate elise;
 
         gen unsafe impl elise::raw::Trace for @Self where
             #bound
+            #(#skipped,)*
+            #(#user_bounds,)*
         {
-            unsafe fn mark(&self) {
-                match self { #mark_body }
-            }
-            unsafe fn manage(&self) {
-                match self { #manage_body }
+            unsafe fn trace(&self, tracer: &mut dyn elise::raw::Tracer) {
+                match self { #trace_body }
             }
             unsafe fn finalize(&mut self) {
                 match self { #finalize_body }
                 #drop_glue
             }
         }
-    })
+    }))
 }
 
-fn has_drop(s: &Structure) -> HasDrop {
-    let finalize = super::has_attr(s, "finalize");
-    let unsafe_finalize = super::has_attr(s, "unsafe_finalize");
-    match (finalize, unsafe_finalize) {
-        (true, true) => panic!("type cannot have both finalize & unsafe_finalize attributes"),
-        (true, false) => HasDrop::Drop,
-        (false, true) => HasDrop::UnsafeDrop,
-        (false, false) => HasDrop::None,
-    }
+fn has_drop(s: &Structure) -> Result<HasDrop> {
+    let options = super::container_attrs(s)?.options;
+    let finalize = options.iter().any(|i| i == "finalize");
+    let unsafe_finalize = options.iter().find(|i| *i == "unsafe_finalize");
+    Ok(match (finalize, unsafe_finalize) {
+        (true, Some(ident)) => {
+            return Err(Error::new_spanned(
+                ident,
+                "type cannot have both finalize & unsafe_finalize attributes",
+            ))
+        }
+        (true, None) => HasDrop::Drop,
+        (false, Some(_)) => HasDrop::UnsafeDrop,
+        (false, None) => HasDrop::None,
+    })
 }
 
-fn only_has_root_lifetime(s: &Structure) -> bool {
-    s.ast()
+fn only_has_root_lifetime(s: &Structure) -> Result<()> {
+    let root = super::root_lifetime(s);
+    let other = s
+        .ast()
         .generics
-        .params
-        .iter()
-        .filter_map(|param| {
-            if let GenericParam::Lifetime(LifetimeDef { lifetime, .. }) = param {
-                Some(lifetime)
-            } else {
-                None
-            }
-        })
-        .all(|l| l.ident == "root")
+        .lifetimes()
+        .map(|param| &param.lifetime)
+        .find(|lifetime| **lifetime != root);
+    match other {
+        Some(lifetime) => Err(Error::new_spanned(
+            lifetime,
+            format!(
+                "GC'd objects with lifetimes other than {} must use UnsafeFinalize",
+                root
+            ),
+        )),
+        None => Ok(()),
+    }
 }
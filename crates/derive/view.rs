@@ -0,0 +1,157 @@
+use proc_macro2::*;
+use syn::*;
+use synstructure::*;
+
+use crate::FieldAttr;
+
+pub fn view(s: &Structure) -> TokenStream {
+    if !super::has_attr(s, "view") {
+        return quote!();
+    }
+
+    let s_ast: &DeriveInput = s.ast();
+    let name = &s_ast.ident;
+    let visibility = &s_ast.vis;
+    let view = Ident::new(&format!("{}Ref", name), Span::call_site());
+
+    // Every field of the view borrows from the rooted object for '__root
+    let bounds: Vec<TokenStream> = s
+        .variants()
+        .iter()
+        .flat_map(|v| v.bindings())
+        .map(|b| {
+            let ty = &b.ast().ty;
+            if is_gc(b.ast()) {
+                quote!(#ty: elise::raw::Store<'__root>)
+            } else {
+                quote!(#ty: '__root)
+            }
+        })
+        .collect();
+
+    let params = &s_ast.generics.params;
+    let predicates = s_ast.generics.where_clause.as_ref().map(|w| &w.predicates);
+    let predicates = predicates.into_iter().flat_map(|p| p.iter());
+    let def_where = quote!(where #(#predicates,)* #(#bounds,)*);
+
+    let args = params.iter().map(|param| match param {
+        GenericParam::Lifetime(lt) => {
+            let lt = &lt.lifetime;
+            quote!(#lt)
+        }
+        GenericParam::Type(ty) => {
+            let ty = &ty.ident;
+            quote!(#ty)
+        }
+        GenericParam::Const(konst) => {
+            let konst = &konst.ident;
+            quote!(#konst)
+        }
+    });
+    let view_ty = quote!(#view<'__root, #(#args),*>);
+
+    let (def, body) = match &s_ast.data {
+        Data::Enum(data) => {
+            let defs = data.variants.iter().map(|v| {
+                let ident = &v.ident;
+                let fields = view_fields(&v.fields);
+                quote!(#ident #fields)
+            });
+            let def = quote! {
+                #visibility enum #view<'__root, #params> #def_where {
+                    #(#defs,)*
+                }
+            };
+            let arms = s.variants().iter().map(|v| {
+                let pat = v.pat();
+                let ident = &v.ast().ident;
+                let construct = construct(v);
+                quote!(#pat => #view::#ident #construct,)
+            });
+            (def, quote!(#(#arms)*))
+        }
+        _ => {
+            let variant = &s.variants()[0];
+            let fields = view_fields(variant.ast().fields);
+            let def = match variant.ast().fields {
+                Fields::Named(_) => quote! {
+                    #visibility struct #view<'__root, #params> #def_where #fields
+                },
+                _ => quote! {
+                    #visibility struct #view<'__root, #params> #fields #def_where;
+                },
+            };
+            let pat = variant.pat();
+            let construct = construct(variant);
+            (def, quote!(#pat => #view #construct,))
+        }
+    };
+
+    let (impl_generics, ty_generics, where_clauses) = s_ast.generics.split_for_impl();
+
+    quote! {
+        #def
+
+        impl #impl_generics #name #ty_generics #where_clauses {
+            /// Borrow every field of this object at once, with `#[gc]`
+            /// fields rooted through their accessor.
+            #visibility fn view<'__root>(self: &'__root elise::Gc<'__root, Self>) -> #view_ty
+                #def_where
+            {
+                match **self {
+                    #body
+                }
+            }
+        }
+    }
+}
+
+fn is_gc(field: &Field) -> bool {
+    matches!(super::checked_field_attr(field), FieldAttr::Accessor)
+}
+
+fn view_field(field: &Field) -> TokenStream {
+    let ty = &field.ty;
+    let ty = if is_gc(field) {
+        quote!(<#ty as elise::raw::Store<'__root>>::Accessor)
+    } else {
+        quote!(&'__root #ty)
+    };
+    match &field.ident {
+        Some(ident) => quote!(#ident: #ty),
+        None => ty,
+    }
+}
+
+fn view_fields(fields: &Fields) -> TokenStream {
+    match fields {
+        Fields::Named(fields) => {
+            let fields = fields.named.iter().map(view_field);
+            quote!({ #(#fields,)* })
+        }
+        Fields::Unnamed(fields) => {
+            let fields = fields.unnamed.iter().map(view_field);
+            quote!((#(#fields,)*))
+        }
+        Fields::Unit => quote!(),
+    }
+}
+
+fn construct(variant: &VariantInfo) -> TokenStream {
+    let values = variant.bindings().iter().map(|b| {
+        let value = if is_gc(b.ast()) {
+            quote!(unsafe { elise::raw::Store::rooted(#b) })
+        } else {
+            quote!(#b)
+        };
+        match &b.ast().ident {
+            Some(ident) => quote!(#ident: #value),
+            None => value,
+        }
+    });
+    match variant.ast().fields {
+        Fields::Named(_) => quote!({ #(#values,)* }),
+        Fields::Unnamed(_) => quote!((#(#values,)*)),
+        Fields::Unit => quote!(),
+    }
+}
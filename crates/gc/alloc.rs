@@ -1,116 +1,287 @@
-use std::mem;
+use std::alloc::{self, Layout};
+use std::marker::PhantomData;
 use std::ops::{Deref, DerefMut};
-use std::ptr::NonNull;
-use std::sync::atomic::{AtomicBool, Ordering::*};
+use std::ptr::{self, NonNull};
+use std::sync::atomic::{AtomicU8, AtomicUsize, Ordering::*};
 
 use log::*;
 
-use crate::trace::Trace;
+use crate::trace::{Trace, Tracer};
 
 pub struct Data {
     _priv: (),
 }
 
+/// How to handle the data of an allocation without knowing its type
+///
+/// The data is made of `len` elements, which is a single one unless it was
+/// allocated as a slice or a string.
 struct Vtable {
-    _priv: (),
+    element: Layout,
+    trace: unsafe fn(*const u8, &mut dyn Tracer),
+    finalize: unsafe fn(*mut u8),
+    type_name: unsafe fn(*const u8) -> &'static str,
+}
+
+/// The vtables of allocations of a `T`, or of a slice `S` of `T`s
+struct Vtables<S: ?Sized, T>(PhantomData<*const S>, PhantomData<T>);
+
+impl<S: ?Sized, T: Trace> Vtables<S, T> {
+    const SINGLE: Vtable = Vtable {
+        element: Layout::new::<T>(),
+        trace: trace_element::<T>,
+        finalize: finalize_element::<T>,
+        type_name: element_type_name::<T>,
+    };
+
+    const SLICE: Vtable = Vtable {
+        element: Layout::new::<T>(),
+        trace: trace_element::<T>,
+        finalize: finalize_element::<T>,
+        type_name: slice_type_name::<S>,
+    };
+}
+
+unsafe fn trace_element<T: Trace>(element: *const u8, tracer: &mut dyn Tracer) {
+    (*(element as *const T)).trace(tracer)
+}
+
+unsafe fn finalize_element<T: Trace>(element: *mut u8) {
+    (*(element as *mut T)).finalize()
 }
 
+unsafe fn element_type_name<T: Trace>(element: *const u8) -> &'static str {
+    (*(element as *const T)).type_name()
+}
+
+unsafe fn slice_type_name<S: ?Sized>(_: *const u8) -> &'static str {
+    std::any::type_name::<S>()
+}
+
+#[repr(C)]
 pub struct Allocation<T: ?Sized> {
     header: Header,
     pub(crate) data: T,
 }
 
+/// The next identity to hand out, 0 meaning that none was handed out yet
+static IDENTITY: AtomicUsize = AtomicUsize::new(1);
+
+/// Where an allocation is in its lifecycle
+///
+/// An allocation starts out unmanaged, owned by whatever allocated it, and
+/// becomes managed once it is reachable from a managed object or a root. From
+/// then on the collector owns it: each collection marks it if it is reachable,
+/// then sweeps it, unmarking it if it was marked and freeing it otherwise.
+#[repr(u8)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum State {
+    Unmanaged,
+    Managed,
+    Marked,
+}
+
+impl State {
+    fn from_u8(state: u8) -> State {
+        match state {
+            0 => State::Unmanaged,
+            1 => State::Managed,
+            2 => State::Marked,
+            _ => unreachable!("invalid allocation state {}", state),
+        }
+    }
+}
+
 struct Header {
-    vtable: *mut Vtable,
-    marked: AtomicBool,
-    managed: AtomicBool,
+    vtable: &'static Vtable,
+    len: usize,
+    identity: AtomicUsize,
+    state: AtomicU8,
+}
+
+impl Header {
+    fn new(vtable: &'static Vtable, len: usize) -> Header {
+        Header {
+            vtable,
+            len,
+            identity: AtomicUsize::new(0),
+            state: AtomicU8::new(State::Unmanaged as u8),
+        }
+    }
+}
+
+/// The layout of an allocation of `len` elements
+fn layout(element: Layout, len: usize) -> Layout {
+    let size = element
+        .size()
+        .checked_mul(len)
+        .expect("allocation too large");
+    let data = Layout::from_size_align(size, element.align()).unwrap();
+    Layout::new::<Header>()
+        .extend(data)
+        .unwrap()
+        .0
+        .pad_to_align()
 }
 
 impl<T: Trace> Allocation<T> {
     pub fn new(data: T) -> Ptr<Allocation<T>> {
-        let vtable = extract_vtable(&data);
-
         let allocation = Box::new(Allocation {
-            header: Header {
-                vtable,
-                marked: AtomicBool::new(false),
-                managed: AtomicBool::new(false),
-            },
+            header: Header::new(&Vtables::<T, T>::SINGLE, 1),
             data,
         });
         unsafe { Ptr(NonNull::new_unchecked(Box::into_raw(allocation))) }
     }
 }
 
+impl<T: Trace> Allocation<[T]> {
+    /// Allocate the elements of `data` inline, right after the header
+    pub fn new_slice(data: Vec<T>) -> Ptr<Allocation<[T]>> {
+        Allocation::alloc_slice(&Vtables::<[T], T>::SLICE, data)
+    }
+
+    fn alloc_slice(vtable: &'static Vtable, mut data: Vec<T>) -> Ptr<Allocation<[T]>> {
+        let len = data.len();
+        let layout = layout(Layout::new::<T>(), len);
+        unsafe {
+            let raw = alloc::alloc(layout);
+            if raw.is_null() {
+                alloc::handle_alloc_error(layout)
+            }
+            let ptr = ptr::slice_from_raw_parts_mut(raw as *mut T, len) as *mut Allocation<[T]>;
+            ptr::addr_of_mut!((*ptr).header).write(Header::new(vtable, len));
+            let elements = ptr::addr_of_mut!((*ptr).data) as *mut T;
+            ptr::copy_nonoverlapping(data.as_ptr(), elements, len);
+            // The elements now belong to the allocation
+            data.set_len(0);
+            Ptr(NonNull::new_unchecked(ptr))
+        }
+    }
+}
+
+impl Allocation<str> {
+    /// Allocate a copy of `data` inline, right after the header
+    pub fn new_str(data: &str) -> Ptr<Allocation<str>> {
+        let vtable = &Vtables::<str, u8>::SLICE;
+        let bytes = Allocation::alloc_slice(vtable, data.as_bytes().to_vec());
+        unsafe {
+            Ptr(NonNull::new_unchecked(
+                bytes.as_ptr() as *mut Allocation<str>
+            ))
+        }
+    }
+}
+
 impl Allocation<Data> {
     pub unsafe fn free(this: *mut Allocation<Data>) {
-        (&mut *this).dyn_data_mut().finalize();
-        drop(Box::from_raw(this))
+        let Header { vtable, len, .. } = (*this).header;
+        for index in 0..len {
+            (vtable.finalize)((*this).element(index) as *mut u8);
+        }
+        alloc::dealloc(this as *mut u8, layout(vtable.element, len))
     }
 }
 
 impl<T: ?Sized> Allocation<T> {
-    pub unsafe fn mark(&self) {
+    pub fn state(&self) -> State {
+        State::from_u8(self.header.state.load(Acquire))
+    }
+
+    /// Move from the `from` state to the `to` state, returning the state it
+    /// was in instead if it was not in `from`
+    fn transition(&self, from: State, to: State) -> Result<(), State> {
+        self.header
+            .state
+            .compare_exchange(from as u8, to as u8, AcqRel, Acquire)
+            .map(drop)
+            .map_err(State::from_u8)
+    }
+
+    /// Hand this allocation to the collector, returning whether it was
+    /// unmanaged before
+    ///
+    /// Stores can point to objects which are already managed, so managing
+    /// them again is not a mistake.
+    pub fn manage(&self) -> bool {
+        match self.transition(State::Unmanaged, State::Managed) {
+            Ok(()) => true,
+            Err(state) => {
+                debug_assert_eq!(state, State::Managed, "managed a marked object");
+                false
+            }
+        }
+    }
+
+    /// Mark this allocation as reachable, returning whether it was unmarked
+    pub fn mark(&self) -> bool {
         debug!(
             "MARKING object at:          {:x}",
-            self.erased() as *const _ as usize
+            self as *const Self as *const u8 as usize
         );
-        if !self.header.marked.swap(true, AcqRel) {
-            self.dyn_data().mark()
+        match self.transition(State::Managed, State::Marked) {
+            Ok(()) => true,
+            Err(state) => {
+                debug_assert_eq!(state, State::Marked, "marked an unmanaged object");
+                false
+            }
         }
     }
 
-    pub fn data(&self) -> &T {
-        &self.data
+    /// Unmark this allocation after a collection, returning whether it
+    /// survives it
+    pub fn sweep(&self) -> bool {
+        match self.transition(State::Marked, State::Managed) {
+            Ok(()) => true,
+            Err(state) => {
+                debug_assert_eq!(state, State::Managed, "swept an unmanaged object");
+                false
+            }
+        }
     }
 
-    pub fn marked(&self) -> bool {
-        self.header.marked.swap(false, AcqRel)
+    pub unsafe fn trace(&self, tracer: &mut dyn Tracer) {
+        for index in 0..self.header.len {
+            (self.header.vtable.trace)(self.element(index), tracer)
+        }
     }
 
-    pub fn is_unmanaged(&self) -> bool {
-        self.header.marked.load(Acquire)
+    pub fn data(&self) -> &T {
+        &self.data
     }
 
-    pub fn managed(&self) {
-        self.header.managed.store(true, Release);
+    pub fn is_unmanaged(&self) -> bool {
+        self.state() == State::Unmanaged
     }
 
-    fn dyn_data(&self) -> &dyn Trace {
-        unsafe {
-            let object = Object {
-                data: self.erased().data() as *const Data,
-                vtable: self.header.vtable,
-            };
-            mem::transmute::<Object, &dyn Trace>(object)
+    /// A number identifying this allocation for as long as it lives
+    ///
+    /// Unlike the address, it would not change if the allocation was moved.
+    pub fn identity(&self) -> usize {
+        let identity = self.header.identity.load(Acquire);
+        if identity != 0 {
+            return identity;
         }
-    }
-
-    fn dyn_data_mut(&mut self) -> &mut dyn Trace {
-        unsafe {
-            let object = Object {
-                data: self.erased().data() as *const Data,
-                vtable: self.header.vtable,
-            };
-            mem::transmute::<Object, &mut dyn Trace>(object)
+        let new = IDENTITY.fetch_add(1, Relaxed);
+        match self
+            .header
+            .identity
+            .compare_exchange(0, new, AcqRel, Acquire)
+        {
+            Ok(_) => new,
+            Err(identity) => identity,
         }
     }
 
-    fn erased(&self) -> &Allocation<Data> {
-        unsafe { &*(self as *const Allocation<T> as *const Allocation<Data>) }
+    /// The name of the type of the data, or of the slice it is part of
+    pub(crate) fn type_name(&self) -> &'static str {
+        unsafe { (self.header.vtable.type_name)(self.element(0)) }
     }
-}
 
-#[repr(C)]
-struct Object {
-    data: *const Data,
-    vtable: *mut Vtable,
-}
-
-fn extract_vtable<T: Trace>(data: &T) -> *mut Vtable {
-    unsafe {
-        let obj = data as &dyn Trace;
-        mem::transmute::<&dyn Trace, Object>(obj).vtable
+    fn element(&self, index: usize) -> *const u8 {
+        let element = self.header.vtable.element;
+        let (_, offset) = Layout::new::<Header>().extend(element).unwrap();
+        let offset = offset + index * element.size();
+        unsafe { (self as *const Self as *const u8).add(offset) }
     }
 }
 
@@ -123,6 +294,9 @@ impl<T: ?Sized> Clone for Ptr<T> {
     }
 }
 
+#[cfg(feature = "nightly")]
+impl<T: ?Sized + std::marker::Unsize<U>, U: ?Sized> std::ops::CoerceUnsized<Ptr<U>> for Ptr<T> {}
+
 impl<T: ?Sized> Deref for Ptr<T> {
     type Target = NonNull<T>;
 
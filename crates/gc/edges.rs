@@ -0,0 +1,79 @@
+use std::vec;
+
+use crate::gc_ptr::{ErasedPtr, GcPtr};
+use crate::trace::{Trace, Tracer};
+
+/// An outgoing edge of an object
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Edge {
+    /// The field the edge was found in, if it is known
+    pub field: Option<&'static str>,
+    /// The allocation the edge points to
+    pub ptr: ErasedPtr,
+}
+
+/// Iterator over the outgoing edges of an object, as returned by `edges`
+pub struct Edges {
+    edges: vec::IntoIter<Edge>,
+}
+
+impl Iterator for Edges {
+    type Item = Edge;
+
+    fn next(&mut self) -> Option<Edge> {
+        self.edges.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.edges.size_hint()
+    }
+}
+
+impl ExactSizeIterator for Edges {}
+
+/// List the GC pointers directly owned by the data behind `ptr`
+///
+/// # Safety
+///
+/// `ptr` must not be dangling.
+pub unsafe fn edges<T: Trace + ?Sized>(ptr: GcPtr<T>) -> Edges {
+    ptr.erase().edges()
+}
+
+impl ErasedPtr {
+    /// List the GC pointers directly owned by the data behind this ptr
+    ///
+    /// # Safety
+    ///
+    /// This ptr must not be dangling.
+    pub unsafe fn edges(self) -> Edges {
+        let mut collector = Collector {
+            edges: vec![],
+            fields: vec![],
+        };
+        self.trace(&mut collector);
+        Edges {
+            edges: collector.edges.into_iter(),
+        }
+    }
+}
+
+struct Collector {
+    edges: Vec<Edge>,
+    fields: Vec<&'static str>,
+}
+
+impl Tracer for Collector {
+    unsafe fn visit(&mut self, ptr: ErasedPtr) {
+        let field = self.fields.last().cloned();
+        self.edges.push(Edge { field, ptr });
+    }
+
+    fn enter_field(&mut self, name: &'static str) {
+        self.fields.push(name);
+    }
+
+    fn exit_field(&mut self) {
+        self.fields.pop();
+    }
+}
@@ -1,8 +1,9 @@
+use std::fmt;
 use std::pin::Pin;
 use std::ptr::NonNull;
 
-use crate::alloc::{Allocation, Data, Ptr};
-use crate::trace::Trace;
+use crate::alloc::{Allocation, Data, Ptr, State};
+use crate::trace::{Trace, Tracer};
 
 pub struct GcPtr<T: ?Sized> {
     inner: Ptr<Allocation<T>>,
@@ -16,28 +17,110 @@ impl<T: Trace> GcPtr<T> {
     }
 }
 
+impl<T: Trace> GcPtr<[T]> {
+    pub(crate) fn new_slice(data: Vec<T>) -> GcPtr<[T]> {
+        GcPtr {
+            inner: Allocation::new_slice(data),
+        }
+    }
+}
+
+impl GcPtr<str> {
+    pub(crate) fn new_str(data: &str) -> GcPtr<str> {
+        GcPtr {
+            inner: Allocation::new_str(data),
+        }
+    }
+}
+
 impl<T: ?Sized> GcPtr<T> {
     /// Get a reference to the GC'd data
     ///
-    /// Invariants: GcPtr must not be dangling
+    /// # Safety
+    ///
+    /// This GcPtr must not be dangling.
     pub unsafe fn data(&self) -> &T {
         self.inner.as_ref().data()
     }
 
     /// Tell if this ptr is managed or not
     ///
-    /// Invariants: GcPtr must not be dangling
+    /// # Safety
+    ///
+    /// This GcPtr must not be dangling.
     pub unsafe fn is_unmanaged(&self) -> bool {
         self.inner.as_ref().is_unmanaged()
     }
 
+    /// Where the allocation behind this ptr is in its lifecycle
+    ///
+    /// # Safety
+    ///
+    /// This GcPtr must not be dangling.
+    pub unsafe fn state(&self) -> State {
+        self.inner.as_ref().state()
+    }
+
+    /// Tell if both GcPtrs point to the same allocation
+    pub fn ptr_eq<U: ?Sized>(self, other: GcPtr<U>) -> bool {
+        self.erase() == other.erase()
+    }
+
+    /// A number identifying the allocation behind this GcPtr for as long as it lives
+    ///
+    /// # Safety
+    ///
+    /// This GcPtr must not be dangling.
+    pub unsafe fn identity(&self) -> usize {
+        self.inner.as_ref().identity()
+    }
+
     /// Free the data behind this GcPtr
     ///
-    /// Invariants: GcPtr must not be dangling, must not be managed and must not be read again
+    /// # Safety
+    ///
+    /// This GcPtr must not be dangling, must not be managed and must not be read
+    /// again.
     pub unsafe fn deallocate(self) {
         drop(Box::from_raw(self.inner.as_ptr()))
     }
 
+    /// Convert this GcPtr to point to an unsized type, such as a trait object
+    ///
+    /// The allocation keeps its own vtable for tracing and finalizing, so
+    /// those are unaffected.
+    ///
+    /// # Safety
+    ///
+    /// `coerce` must only apply an unsizing coercion to the pointer it is
+    /// given, which it must not read through.
+    pub unsafe fn coerce<U: ?Sized>(self, coerce: impl FnOnce(*const T) -> *const U) -> GcPtr<U> {
+        let ptr = coerce(self.inner.as_ptr() as *const T);
+        GcPtr {
+            inner: Ptr(NonNull::new_unchecked(ptr as *mut Allocation<U>)),
+        }
+    }
+
+    /// Cast this GcPtr to point to another type
+    ///
+    /// # Safety
+    ///
+    /// The data behind this GcPtr must be of type `U`.
+    pub unsafe fn cast<U>(self) -> GcPtr<U> {
+        GcPtr {
+            inner: Ptr(NonNull::new_unchecked(
+                self.inner.as_ptr() as *mut Allocation<U>
+            )),
+        }
+    }
+
+    /// Forget the type of the data behind this GcPtr
+    pub fn erase(self) -> ErasedPtr {
+        ErasedPtr {
+            inner: self.erased(),
+        }
+    }
+
     pub(crate) fn erased(self) -> Ptr<Allocation<Data>> {
         unsafe {
             Ptr(NonNull::new_unchecked(
@@ -45,22 +128,14 @@ impl<T: ?Sized> GcPtr<T> {
             ))
         }
     }
-
-    pub(crate) unsafe fn erased_pinned<'a>(self) -> Pin<&'a Allocation<Data>> {
-        Pin::new_unchecked(&*self.erased().as_ptr())
-    }
 }
 
 unsafe impl<T: ?Sized + Send> Send for GcPtr<T> {}
 unsafe impl<T: ?Sized + Sync> Sync for GcPtr<T> {}
 
 unsafe impl<T: Trace + ?Sized> Trace for GcPtr<T> {
-    unsafe fn mark(&self) {
-        self.inner.as_ref().mark();
-    }
-
-    unsafe fn manage(&self) {
-        super::manage(*self)
+    unsafe fn trace(&self, tracer: &mut dyn Tracer) {
+        tracer.visit(self.erase())
     }
 
     unsafe fn finalize(&mut self) {}
@@ -73,3 +148,77 @@ impl<T: ?Sized> Clone for GcPtr<T> {
 }
 
 impl<T: ?Sized> Copy for GcPtr<T> {}
+
+#[cfg(feature = "nightly")]
+impl<T: ?Sized + std::marker::Unsize<U>, U: ?Sized> std::ops::CoerceUnsized<GcPtr<U>> for GcPtr<T> {}
+
+/// A GcPtr to data of an unknown type
+#[derive(Copy, Clone)]
+pub struct ErasedPtr {
+    inner: Ptr<Allocation<Data>>,
+}
+
+impl ErasedPtr {
+    /// The address of the allocation, which identifies it
+    pub fn addr(self) -> usize {
+        self.inner.as_ptr() as usize
+    }
+
+    /// A number identifying the allocation for as long as it lives
+    ///
+    /// # Safety
+    ///
+    /// This ErasedPtr must not be dangling.
+    pub unsafe fn identity(self) -> usize {
+        self.inner.as_ref().identity()
+    }
+
+    /// Get the type name of the data behind this ptr
+    ///
+    /// # Safety
+    ///
+    /// This ErasedPtr must not be dangling.
+    pub unsafe fn type_name(self) -> &'static str {
+        self.inner.as_ref().type_name()
+    }
+
+    /// Trace the data behind this ptr
+    ///
+    /// # Safety
+    ///
+    /// This ErasedPtr must not be dangling.
+    pub unsafe fn trace(self, tracer: &mut dyn Tracer) {
+        self.inner.as_ref().trace(tracer)
+    }
+
+    pub(crate) unsafe fn allocation<'a>(self) -> Pin<&'a Allocation<Data>> {
+        Pin::new_unchecked(&*self.inner.as_ptr())
+    }
+
+    pub(crate) fn ptr(self) -> Ptr<Allocation<Data>> {
+        self.inner
+    }
+}
+
+impl PartialEq for ErasedPtr {
+    fn eq(&self, other: &ErasedPtr) -> bool {
+        self.addr() == other.addr()
+    }
+}
+
+impl Eq for ErasedPtr {}
+
+impl std::hash::Hash for ErasedPtr {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.addr().hash(state)
+    }
+}
+
+impl fmt::Debug for ErasedPtr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "ErasedPtr({:x})", self.addr())
+    }
+}
+
+unsafe impl Send for ErasedPtr {}
+unsafe impl Sync for ErasedPtr {}
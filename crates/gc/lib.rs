@@ -1,18 +1,26 @@
+#![cfg_attr(feature = "nightly", feature(coerce_unsized, unsize))]
+
 mod alloc;
+mod edges;
 mod gc_ptr;
+mod path;
 mod root;
 mod state;
 mod trace;
 
+use std::panic::Location;
 use std::pin::Pin;
 
 use once_cell::sync::Lazy;
 
 use crate::state::GcState;
 
-pub use crate::gc_ptr::GcPtr;
-pub use crate::root::Root;
-pub use crate::trace::{NullTrace, Trace};
+pub use crate::alloc::State;
+pub use crate::edges::{edges, Edge, Edges};
+pub use crate::gc_ptr::{ErasedPtr, GcPtr};
+pub use crate::path::{retaining_path, PathStep, RetainingPath};
+pub use crate::root::{Root, RootInfo};
+pub use crate::trace::{trace_field, NullTrace, Trace, Tracer};
 
 static GC: Lazy<GcState> = Lazy::new(|| GcState::default());
 
@@ -21,6 +29,16 @@ pub fn alloc_unmanaged<T: Trace>(data: T) -> GcPtr<T> {
     GcPtr::new(data)
 }
 
+/// Allocate an unmanaged GcPtr to a slice, with the elements stored inline
+pub fn alloc_unmanaged_slice<T: Trace>(data: Vec<T>) -> GcPtr<[T]> {
+    GcPtr::new_slice(data)
+}
+
+/// Allocate an unmanaged GcPtr to a copy of a string, stored inline
+pub fn alloc_unmanaged_str(data: &str) -> GcPtr<str> {
+    GcPtr::new_str(data)
+}
+
 /// Allocate a managed GcPtr
 pub fn alloc<T: Trace>(data: T) -> GcPtr<T> {
     let gc_ptr = alloc_unmanaged(data);
@@ -47,8 +65,20 @@ pub fn count_roots() -> usize {
     with_gc(|gc| gc.count_roots())
 }
 
-fn set_root<T: Trace + ?Sized>(idx: usize, ptr: GcPtr<T>) {
-    with_gc(|gc| gc.set_root(idx, ptr))
+/// List the live roots into the GC
+///
+/// Creation sites are only recorded in debug builds.
+pub fn live_roots() -> Vec<RootInfo> {
+    with_gc(|gc| gc.root_infos())
+}
+
+fn set_root(
+    idx: usize,
+    ptr: ErasedPtr,
+    type_name: &'static str,
+    location: Option<&'static Location<'static>>,
+) {
+    with_gc(|gc| gc.set_root(idx, ptr, type_name, location))
 }
 
 fn pop_root(idx: usize) {
@@ -0,0 +1,120 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::gc_ptr::{ErasedPtr, GcPtr};
+use crate::root::RootInfo;
+use crate::trace::Tracer;
+
+/// One allocation on a retaining path
+#[derive(Clone, Debug)]
+pub struct PathStep {
+    /// Address of the allocation
+    pub addr: usize,
+    /// Type name of the object in the allocation
+    pub type_name: &'static str,
+    /// The field of the previous step this allocation was reached through, if
+    /// it is known
+    pub field: Option<&'static str>,
+}
+
+/// The chain of allocations from a root to an object, as returned by
+/// `retaining_path`
+#[derive(Clone, Debug)]
+pub struct RetainingPath {
+    /// The root the path starts from
+    pub root: RootInfo,
+    /// The allocations on the path, from the rooted object to the target
+    pub steps: Vec<PathStep>,
+}
+
+impl fmt::Display for RetainingPath {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.root)?;
+        for step in &self.steps {
+            match step.field {
+                Some(field) => write!(f, "\n  .{} -> ", field)?,
+                None => write!(f, "\n  -> ")?,
+            }
+            write!(f, "{} at {:x}", step.type_name, step.addr)?;
+        }
+        Ok(())
+    }
+}
+
+/// Find a path from a root to the allocation behind `ptr`
+///
+/// # Safety
+///
+/// `ptr` must not be dangling.
+pub unsafe fn retaining_path<T: ?Sized>(ptr: GcPtr<T>) -> Option<RetainingPath> {
+    let mut search = Search {
+        target: ptr.erase(),
+        parents: HashMap::new(),
+        stack: vec![],
+        fields: vec![],
+        found: false,
+    };
+
+    let root = crate::with_gc(|gc| gc.root_ptrs())
+        .into_iter()
+        .find(|(_, ptr)| {
+            search.visit(*ptr);
+            search.found
+        })?
+        .0;
+
+    let mut steps = vec![];
+    let mut next = Some(search.target);
+    while let Some(ptr) = next {
+        let (parent, field) = search.parents[&ptr];
+        steps.push(PathStep {
+            addr: ptr.addr(),
+            type_name: ptr.type_name(),
+            field,
+        });
+        next = parent;
+    }
+    steps.reverse();
+
+    Some(RetainingPath { root, steps })
+}
+
+/// Walks the graph depth first, remembering how each allocation was reached
+struct Search {
+    target: ErasedPtr,
+    parents: HashMap<ErasedPtr, (Option<ErasedPtr>, Option<&'static str>)>,
+    stack: Vec<ErasedPtr>,
+    fields: Vec<Option<&'static str>>,
+    found: bool,
+}
+
+impl Tracer for Search {
+    unsafe fn visit(&mut self, ptr: ErasedPtr) {
+        if self.found || self.parents.contains_key(&ptr) {
+            return;
+        }
+
+        let parent = self.stack.last().cloned();
+        let field = self.fields.last().cloned().flatten();
+        self.parents.insert(ptr, (parent, field));
+
+        if ptr == self.target {
+            self.found = true;
+            return;
+        }
+
+        self.stack.push(ptr);
+        self.fields.push(None);
+        ptr.trace(self);
+        self.fields.pop();
+        self.stack.pop();
+    }
+
+    fn enter_field(&mut self, name: &'static str) {
+        self.fields.push(Some(name));
+    }
+
+    fn exit_field(&mut self) {
+        self.fields.pop();
+    }
+}
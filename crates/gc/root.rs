@@ -1,8 +1,10 @@
+use std::fmt;
+use std::panic::Location;
 use std::sync::atomic::{AtomicUsize, Ordering};
 
 use crossbeam::queue::SegQueue;
 
-use crate::gc_ptr::GcPtr;
+use crate::gc_ptr::{ErasedPtr, GcPtr};
 use crate::trace::Trace;
 
 static COUNTER: AtomicUsize = AtomicUsize::new(0);
@@ -10,19 +12,43 @@ static RECYCLE: SegQueue<usize> = SegQueue::new();
 
 pub struct Root {
     idx: usize,
+    #[cfg(debug_assertions)]
+    location: &'static Location<'static>,
 }
 
 impl Root {
+    #[track_caller]
     pub fn new() -> Root {
         Root {
             idx: RECYCLE
                 .pop()
                 .unwrap_or(COUNTER.fetch_add(1, Ordering::Relaxed)),
+            #[cfg(debug_assertions)]
+            location: Location::caller(),
         }
     }
 
     pub unsafe fn enroot<T: Trace + ?Sized>(&self, gc_ptr: GcPtr<T>) {
-        super::set_root(self.idx, gc_ptr)
+        let type_name = std::any::type_name::<T>();
+        super::set_root(self.idx, gc_ptr.erase(), type_name, self.location())
+    }
+
+    /// Root an allocation whose type is only known at runtime
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must not be dangling. Like `enroot`, the allocation is then kept
+    /// alive until this root is dropped or points at another allocation.
+    pub unsafe fn enroot_erased(&self, ptr: ErasedPtr) {
+        super::set_root(self.idx, ptr, ptr.type_name(), self.location())
+    }
+
+    /// Where this root was created, in debug builds
+    pub fn location(&self) -> Option<&'static Location<'static>> {
+        #[cfg(debug_assertions)]
+        return Some(self.location);
+        #[cfg(not(debug_assertions))]
+        return None;
     }
 }
 
@@ -32,3 +58,30 @@ impl Drop for Root {
         super::pop_root(self.idx);
     }
 }
+
+/// A snapshot of a live root, as returned by `live_roots`
+#[derive(Clone, Debug)]
+pub struct RootInfo {
+    /// Index of the root in the root table
+    pub idx: usize,
+    /// Address of the rooted allocation
+    pub addr: usize,
+    /// Type name of the rooted object
+    pub type_name: &'static str,
+    /// Where the root was created; only recorded in debug builds
+    pub location: Option<&'static Location<'static>>,
+}
+
+impl fmt::Display for RootInfo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "root {:x}: {} at {:x}",
+            self.idx, self.type_name, self.addr
+        )?;
+        match self.location {
+            Some(location) => write!(f, " (created at {})", location),
+            None => write!(f, " (creation site unknown)"),
+        }
+    }
+}
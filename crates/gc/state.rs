@@ -1,33 +1,39 @@
+use std::panic::Location;
 use std::pin::Pin;
-use std::ptr::NonNull;
 
 use crossbeam::queue::SegQueue;
 use dashmap::iter::Iter;
 use dashmap::DashMap;
 use log::*;
 
-use crate::alloc::{Allocation, Data, Ptr};
-use crate::gc_ptr::GcPtr;
-use crate::trace::Trace;
+use crate::alloc::{Allocation, Data, Ptr, State};
+use crate::gc_ptr::{ErasedPtr, GcPtr};
+use crate::root::RootInfo;
+use crate::trace::{Trace, Tracer};
 
 #[derive(Default)]
 pub struct GcState {
     objects: SegQueue<Ptr<Allocation<Data>>>,
-    roots: DashMap<usize, Option<Ptr<Allocation<Data>>>>,
+    roots: DashMap<usize, RootEntry>,
+}
+
+pub struct RootEntry {
+    ptr: ErasedPtr,
+    type_name: &'static str,
+    location: Option<&'static Location<'static>>,
 }
 
 impl GcState {
     pub fn collect(self: Pin<&Self>) {
         for pair in self.roots() {
-            if let Some(root) = pair.value() {
-                debug!(
-                    "TRACING from root at:       {:x} (idx {:x})",
-                    &*root as *const _ as usize,
-                    pair.key()
-                );
-                unsafe {
-                    root.as_ref().mark();
-                }
+            let root = pair.value().ptr;
+            debug!(
+                "TRACING from root at:       {:x} (idx {:x})",
+                root.addr(),
+                pair.key()
+            );
+            unsafe {
+                Marker { gc: self }.visit(root);
             }
         }
 
@@ -36,7 +42,7 @@ impl GcState {
             match self.objects().pop() {
                 Some(object) => {
                     let ptr = unsafe { object.as_ref() };
-                    if !ptr.marked() {
+                    if !ptr.sweep() {
                         debug!(
                             "FREEING unmarked object at: {:x}",
                             &*object as *const _ as usize
@@ -56,39 +62,64 @@ impl GcState {
     }
 
     pub unsafe fn manage<T: Trace + ?Sized>(self: Pin<&Self>, ptr: GcPtr<T>) {
-        // TODO I should not need a dynamic check here but I am making mistakes
-        if ptr.is_unmanaged() {
-            let ptr = Ptr(NonNull::from(&*ptr.erased_pinned()));
-            ptr.as_ref().managed();
-            self.objects().push(ptr);
-        }
-        ptr.data().manage();
+        Manager { gc: self }.visit(ptr.erase())
     }
 
-    pub fn set_root<T: Trace + ?Sized>(self: Pin<&Self>, idx: usize, ptr: GcPtr<T>) {
-        let root: Ptr<Allocation<Data>> = ptr.erased();
+    pub fn set_root(
+        self: Pin<&Self>,
+        idx: usize,
+        root: ErasedPtr,
+        type_name: &'static str,
+        location: Option<&'static Location<'static>>,
+    ) {
         debug!(
             "ENROOTING root at:          {:x} (idx {:x})",
-            root.as_ptr() as usize,
+            root.addr(),
             idx
         );
-        self.roots.insert(idx, Some(root));
+        let entry = RootEntry {
+            ptr: root,
+            type_name,
+            location,
+        };
+        self.roots.insert(idx, entry);
     }
 
     pub fn pop_root(self: Pin<&Self>, idx: usize) {
-        if let Some((idx, Some(root))) = self.roots.remove(&idx) {
+        if let Some((idx, entry)) = self.roots.remove(&idx) {
             debug!(
                 " DROPPING root at:           {:x} (idx {:x})",
-                root.as_ptr() as usize,
+                entry.ptr.addr(),
                 idx
             );
         }
     }
 
-    pub fn roots(&self) -> Iter<usize, Option<Ptr<Allocation<Data>>>> {
+    pub fn roots(&self) -> Iter<'_, usize, RootEntry> {
         self.roots.iter()
     }
 
+    pub fn root_infos(&self) -> Vec<RootInfo> {
+        self.root_ptrs().into_iter().map(|(info, _)| info).collect()
+    }
+
+    pub fn root_ptrs(&self) -> Vec<(RootInfo, ErasedPtr)> {
+        let mut roots: Vec<_> = self
+            .roots()
+            .map(|pair| {
+                let info = RootInfo {
+                    idx: *pair.key(),
+                    addr: pair.value().ptr.addr(),
+                    type_name: pair.value().type_name,
+                    location: pair.value().location,
+                };
+                (info, pair.value().ptr)
+            })
+            .collect();
+        roots.sort_by_key(|(info, _)| info.idx);
+        roots
+    }
+
     pub fn count_roots(&self) -> usize {
         self.roots.len()
     }
@@ -102,5 +133,41 @@ impl GcState {
     }
 }
 
+/// Marks everything reachable from the allocations it visits
+struct Marker<'a> {
+    gc: Pin<&'a GcState>,
+}
+
+impl<'a> Tracer for Marker<'a> {
+    unsafe fn visit(&mut self, ptr: ErasedPtr) {
+        let allocation = ptr.allocation();
+        // A store put into a managed object without going through a root is
+        // only found here, and is managed so that it is kept like the rest
+        if allocation.state() == State::Unmanaged && allocation.manage() {
+            self.gc.objects().push(ptr.ptr());
+        }
+        if allocation.mark() {
+            ptr.trace(self);
+        }
+    }
+}
+
+/// Hands the allocations it visits, and everything they own, to the GC
+struct Manager<'a> {
+    gc: Pin<&'a GcState>,
+}
+
+impl<'a> Tracer for Manager<'a> {
+    unsafe fn visit(&mut self, ptr: ErasedPtr) {
+        let allocation = ptr.allocation();
+        // Everything an unmanaged object owns is unmanaged too, unless it was
+        // rooted already, so tracing stops at managed objects
+        if allocation.manage() {
+            self.gc.objects().push(ptr.ptr());
+            ptr.trace(self);
+        }
+    }
+}
+
 unsafe impl Send for GcState {}
 unsafe impl Sync for GcState {}
@@ -1,20 +1,56 @@
+use crate::gc_ptr::ErasedPtr;
+
 pub unsafe trait Trace {
-    unsafe fn mark(&self);
-    unsafe fn manage(&self);
+    /// Pass every GC pointer owned by this value to the tracer
+    unsafe fn trace(&self, tracer: &mut dyn Tracer);
     unsafe fn finalize(&mut self);
+
+    /// The name of the concrete type, used when describing the heap
+    fn type_name(&self) -> &'static str {
+        std::any::type_name::<Self>()
+    }
 }
 
 pub unsafe trait NullTrace: Trace {}
 
+/// A traversal over the edges of the object graph
+///
+/// Marking, managing and heap inspection are all implemented as tracers.
+pub trait Tracer {
+    /// Visit an edge to an allocation
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must point to a live allocation.
+    unsafe fn visit(&mut self, ptr: ErasedPtr);
+
+    /// Called before tracing a named field of an object
+    fn enter_field(&mut self, _name: &'static str) {}
+
+    /// Called after tracing a named field of an object
+    fn exit_field(&mut self) {}
+}
+
+/// Trace a named field of an object
+///
+/// # Safety
+///
+/// Every GC pointer owned by `field` must point to a live allocation, as when
+/// calling `Trace::trace`.
+pub unsafe fn trace_field<T: Trace + ?Sized>(
+    tracer: &mut dyn Tracer,
+    name: &'static str,
+    field: &T,
+) {
+    tracer.enter_field(name);
+    field.trace(tracer);
+    tracer.exit_field();
+}
+
 unsafe impl<T: Trace> Trace for Option<T> {
-    unsafe fn mark(&self) {
-        if let Some(inner) = self {
-            inner.mark()
-        }
-    }
-    unsafe fn manage(&self) {
+    unsafe fn trace(&self, tracer: &mut dyn Tracer) {
         if let Some(inner) = self {
-            inner.manage()
+            inner.trace(tracer)
         }
     }
 
@@ -28,16 +64,10 @@ unsafe impl<T: Trace> Trace for Option<T> {
 unsafe impl<T: NullTrace> NullTrace for Option<T> {}
 
 unsafe impl<T: Trace, E: Trace> Trace for Result<T, E> {
-    unsafe fn mark(&self) {
-        match self {
-            Ok(inner) => inner.mark(),
-            Err(error) => error.mark(),
-        }
-    }
-    unsafe fn manage(&self) {
+    unsafe fn trace(&self, tracer: &mut dyn Tracer) {
         match self {
-            Ok(inner) => inner.manage(),
-            Err(error) => error.manage(),
+            Ok(inner) => inner.trace(tracer),
+            Err(error) => error.trace(tracer),
         }
     }
     unsafe fn finalize(&mut self) {
@@ -51,14 +81,9 @@ unsafe impl<T: Trace, E: Trace> Trace for Result<T, E> {
 unsafe impl<T: NullTrace, E: NullTrace> NullTrace for Result<T, E> {}
 
 unsafe impl<T: Trace> Trace for [T] {
-    unsafe fn mark(&self) {
-        for elem in self {
-            elem.mark()
-        }
-    }
-    unsafe fn manage(&self) {
+    unsafe fn trace(&self, tracer: &mut dyn Tracer) {
         for elem in self {
-            elem.manage()
+            elem.trace(tracer)
         }
     }
     unsafe fn finalize(&mut self) {
@@ -70,11 +95,20 @@ unsafe impl<T: Trace> Trace for [T] {
 
 unsafe impl<T: NullTrace> NullTrace for [T] {}
 
+// Borrowed data is owned elsewhere. Only data without GC pointers can be
+// borrowed, so that tracing a traced type through an autoref fails to compile
+// instead of tracing nothing.
+unsafe impl<T: NullTrace + ?Sized> Trace for &T {
+    unsafe fn trace(&self, _: &mut dyn Tracer) {}
+    unsafe fn finalize(&mut self) {}
+}
+
+unsafe impl<T: NullTrace + ?Sized> NullTrace for &T {}
+
 macro_rules!
     trace_simple { ($($t:ty)*) => {$(
         unsafe impl Trace for $t {
-            unsafe fn mark(&self) { }
-            unsafe fn manage(&self) { }
+            unsafe fn trace(&self, _: &mut dyn Tracer) { }
             unsafe fn finalize(&mut self) {
                 ptr::drop_in_place(self as *mut Self)
             }
@@ -120,40 +154,53 @@ trace_simple!(
     std::sync::Barrier
     std::sync::Condvar
     std::sync::Once
+    std::sync::atomic::AtomicBool
+    std::sync::atomic::AtomicI8
+    std::sync::atomic::AtomicI16
+    std::sync::atomic::AtomicI32
+    std::sync::atomic::AtomicI64
+    std::sync::atomic::AtomicIsize
+    std::sync::atomic::AtomicU8
+    std::sync::atomic::AtomicU16
+    std::sync::atomic::AtomicU32
+    std::sync::atomic::AtomicU64
+    std::sync::atomic::AtomicUsize
+    i128    u128
+    std::num::NonZeroI8
+    std::num::NonZeroI16
+    std::num::NonZeroI32
+    std::num::NonZeroI64
+    std::num::NonZeroI128
+    std::num::NonZeroIsize
+    std::num::NonZeroU8
+    std::num::NonZeroU16
+    std::num::NonZeroU32
+    std::num::NonZeroU64
+    std::num::NonZeroU128
+    std::num::NonZeroUsize
+    std::ops::RangeFull
+    std::time::Duration
+    std::time::Instant
+    std::time::SystemTime
 );
 
-macro_rules! trace_arrays {
-    ($($N:expr),*)  => {$(
-        unsafe impl<T: Trace> Trace for [T; $N] {
-            unsafe fn mark(&self) {
-                <_ as AsRef<[T]>>::as_ref(self).mark()
-            }
-            unsafe fn manage(&self) {
-                <_ as AsRef<[T]>>::as_ref(self).manage()
-            }
-            unsafe fn finalize(&mut self) {
-                <_ as AsMut<[T]>>::as_mut(self).finalize()
-            }
-        }
-        unsafe impl<T: NullTrace> NullTrace for [T; $N] { }
-    )*};
+unsafe impl<T: Trace, const N: usize> Trace for [T; N] {
+    unsafe fn trace(&self, tracer: &mut dyn Tracer) {
+        <_ as AsRef<[T]>>::as_ref(self).trace(tracer)
+    }
+    unsafe fn finalize(&mut self) {
+        <_ as AsMut<[T]>>::as_mut(self).finalize()
+    }
 }
 
-trace_arrays! {
-    0o00, 0o01, 0o02, 0o03, 0o04, 0o05, 0o06, 0o07,
-    0o10, 0o11, 0o12, 0o13, 0o14, 0o15, 0o16, 0o17,
-    0o20, 0o21, 0o22, 0o23, 0o24, 0o25, 0o26, 0o27,
-    0o30, 0o31, 0o32, 0o33, 0o34, 0o35, 0o36, 0o37
-}
+unsafe impl<T: NullTrace, const N: usize> NullTrace for [T; N] {}
 
 macro_rules! trace_tuples {
     ($(($($T:ident : $N:tt),*))*) => {$(
         unsafe impl<$($T: Trace,)*> Trace for ($($T,)*) {
-            unsafe fn mark(&self) {
-                $(self.$N.mark();)*
-            }
-            unsafe fn manage(&self) {
-                $(self.$N.manage();)*
+            #[allow(unused_variables)]
+            unsafe fn trace(&self, tracer: &mut dyn Tracer) {
+                $(self.$N.trace(tracer);)*
             }
             unsafe fn finalize(&mut self) {
                 $(self.$N.finalize();)*
@@ -177,22 +224,72 @@ trace_tuples! {
     (A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6, H: 7, I: 8, J: 9)
     (A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6, H: 7, I: 8, J: 9, K: 10)
     (A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6, H: 7, I: 8, J: 9, K: 10, L: 11)
+    (A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6, H: 7, I: 8, J: 9, K: 10, L: 11, M: 12)
+    (A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6, H: 7, I: 8, J: 9, K: 10, L: 11, M: 12, N: 13)
+    (A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6, H: 7, I: 8, J: 9, K: 10, L: 11, M: 12, N: 13, O: 14)
+    (A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6, H: 7, I: 8, J: 9, K: 10, L: 11, M: 12, N: 13, O: 14, P: 15)
 }
 
+use std::alloc::{self, Layout};
 use std::collections::*;
 use std::mem::{self, ManuallyDrop};
 use std::ptr;
+use std::rc::Rc;
+use std::sync::Arc;
 
-unsafe impl<T: Trace> Trace for Vec<T> {
-    unsafe fn mark(&self) {
-        for elem in self {
-            elem.mark();
+unsafe impl<T: Trace + ?Sized> Trace for Box<T> {
+    unsafe fn trace(&self, tracer: &mut dyn Tracer) {
+        (**self).trace(tracer)
+    }
+
+    unsafe fn finalize(&mut self) {
+        // Finalize the contents instead of dropping them, then free the box
+        let raw = Box::into_raw(ptr::read(self));
+        let layout = Layout::for_value(&*raw);
+        (*raw).finalize();
+        if layout.size() != 0 {
+            alloc::dealloc(raw as *mut u8, layout);
+        }
+    }
+}
+
+unsafe impl<T: NullTrace + ?Sized> NullTrace for Box<T> {}
+
+// The contents are only finalized along with their last owner
+unsafe impl<T: Trace> Trace for Rc<T> {
+    unsafe fn trace(&self, tracer: &mut dyn Tracer) {
+        (**self).trace(tracer)
+    }
+
+    unsafe fn finalize(&mut self) {
+        if let Some(mut inner) = Rc::into_inner(ptr::read(self)) {
+            inner.finalize();
+            mem::forget(inner);
+        }
+    }
+}
+
+unsafe impl<T: NullTrace> NullTrace for Rc<T> {}
+
+unsafe impl<T: Trace> Trace for Arc<T> {
+    unsafe fn trace(&self, tracer: &mut dyn Tracer) {
+        (**self).trace(tracer)
+    }
+
+    unsafe fn finalize(&mut self) {
+        if let Some(mut inner) = Arc::into_inner(ptr::read(self)) {
+            inner.finalize();
+            mem::forget(inner);
         }
     }
+}
 
-    unsafe fn manage(&self) {
+unsafe impl<T: NullTrace> NullTrace for Arc<T> {}
+
+unsafe impl<T: Trace> Trace for Vec<T> {
+    unsafe fn trace(&self, tracer: &mut dyn Tracer) {
         for elem in self {
-            elem.manage();
+            elem.trace(tracer);
         }
     }
 
@@ -208,15 +305,9 @@ unsafe impl<T: Trace> Trace for Vec<T> {
 unsafe impl<T: NullTrace> NullTrace for Vec<T> {}
 
 unsafe impl<T: Trace> Trace for VecDeque<T> {
-    unsafe fn mark(&self) {
-        for elem in self {
-            elem.mark();
-        }
-    }
-
-    unsafe fn manage(&self) {
+    unsafe fn trace(&self, tracer: &mut dyn Tracer) {
         for elem in self {
-            elem.manage();
+            elem.trace(tracer);
         }
     }
 
@@ -232,15 +323,9 @@ unsafe impl<T: Trace> Trace for VecDeque<T> {
 unsafe impl<T: NullTrace> NullTrace for VecDeque<T> {}
 
 unsafe impl<T: Trace> Trace for LinkedList<T> {
-    unsafe fn mark(&self) {
-        for elem in self {
-            elem.mark();
-        }
-    }
-
-    unsafe fn manage(&self) {
+    unsafe fn trace(&self, tracer: &mut dyn Tracer) {
         for elem in self {
-            elem.manage();
+            elem.trace(tracer);
         }
     }
 
@@ -256,15 +341,9 @@ unsafe impl<T: Trace> Trace for LinkedList<T> {
 unsafe impl<T: NullTrace> NullTrace for LinkedList<T> {}
 
 unsafe impl<T: Trace + Ord> Trace for BinaryHeap<T> {
-    unsafe fn mark(&self) {
-        for elem in self {
-            elem.mark();
-        }
-    }
-
-    unsafe fn manage(&self) {
+    unsafe fn trace(&self, tracer: &mut dyn Tracer) {
         for elem in self {
-            elem.manage();
+            elem.trace(tracer);
         }
     }
 
@@ -273,7 +352,7 @@ unsafe impl<T: Trace + Ord> Trace for BinaryHeap<T> {
         let iter = mem::transmute::<binary_heap::IntoIter<T>, binary_heap::IntoIter<ManuallyDrop<T>>>(
             iter,
         );
-        iter.for_each(|mut elem| elem.finalize());
+        iter.for_each(|mut elem| (*elem).finalize());
     }
 }
 
@@ -284,15 +363,9 @@ where
     T: Eq + std::hash::Hash + Trace,
     S: std::hash::BuildHasher,
 {
-    unsafe fn mark(&self) {
-        for elem in self {
-            elem.mark();
-        }
-    }
-
-    unsafe fn manage(&self) {
+    unsafe fn trace(&self, tracer: &mut dyn Tracer) {
         for elem in self {
-            elem.manage();
+            elem.trace(tracer);
         }
     }
 
@@ -300,7 +373,7 @@ where
         let iter = IntoIterator::into_iter(ptr::read(self));
         let iter =
             mem::transmute::<hash_set::IntoIter<T>, hash_set::IntoIter<ManuallyDrop<T>>>(iter);
-        iter.for_each(|mut elem| elem.finalize());
+        iter.for_each(|mut elem| (*elem).finalize());
     }
 }
 
@@ -317,17 +390,10 @@ where
     V: Trace,
     S: std::hash::BuildHasher,
 {
-    unsafe fn mark(&self) {
-        for (key, value) in self {
-            key.mark();
-            value.mark();
-        }
-    }
-
-    unsafe fn manage(&self) {
+    unsafe fn trace(&self, tracer: &mut dyn Tracer) {
         for (key, value) in self {
-            key.manage();
-            value.manage();
+            key.trace(tracer);
+            value.trace(tracer);
         }
     }
 
@@ -338,8 +404,8 @@ where
             hash_map::IntoIter<ManuallyDrop<K>, ManuallyDrop<V>>,
         >(iter);
         iter.for_each(|(mut key, mut value)| {
-            key.finalize();
-            value.finalize();
+            (*key).finalize();
+            (*value).finalize();
         });
     }
 }
@@ -356,15 +422,9 @@ unsafe impl<T> Trace for BTreeSet<T>
 where
     T: Eq + Ord + Trace,
 {
-    unsafe fn mark(&self) {
-        for elem in self {
-            elem.mark();
-        }
-    }
-
-    unsafe fn manage(&self) {
+    unsafe fn trace(&self, tracer: &mut dyn Tracer) {
         for elem in self {
-            elem.manage();
+            elem.trace(tracer);
         }
     }
 
@@ -372,7 +432,7 @@ where
         let iter = IntoIterator::into_iter(ptr::read(self));
         let iter =
             mem::transmute::<btree_set::IntoIter<T>, btree_set::IntoIter<ManuallyDrop<T>>>(iter);
-        iter.for_each(|mut elem| elem.finalize());
+        iter.for_each(|mut elem| (*elem).finalize());
     }
 }
 
@@ -383,17 +443,10 @@ where
     K: Eq + Ord + Trace,
     V: Trace,
 {
-    unsafe fn mark(&self) {
+    unsafe fn trace(&self, tracer: &mut dyn Tracer) {
         for (key, value) in self {
-            key.mark();
-            value.mark();
-        }
-    }
-
-    unsafe fn manage(&self) {
-        for (key, value) in self {
-            key.manage();
-            value.manage();
+            key.trace(tracer);
+            value.trace(tracer);
         }
     }
 
@@ -404,8 +457,8 @@ where
             btree_map::IntoIter<ManuallyDrop<K>, ManuallyDrop<V>>,
         >(iter);
         iter.for_each(|(mut key, mut value)| {
-            key.finalize();
-            value.finalize();
+            (*key).finalize();
+            (*value).finalize();
         });
     }
 }
@@ -418,11 +471,10 @@ where
 }
 
 use pin_cell::PinCell;
-use std::cell::{Cell, RefCell};
+use std::cell::{Cell, OnceCell, RefCell};
 
 unsafe impl<T: NullTrace> Trace for Cell<T> {
-    unsafe fn mark(&self) {}
-    unsafe fn manage(&self) {}
+    unsafe fn trace(&self, _: &mut dyn Tracer) {}
     unsafe fn finalize(&mut self) {
         ptr::drop_in_place(self as *mut Self)
     }
@@ -431,8 +483,7 @@ unsafe impl<T: NullTrace> Trace for Cell<T> {
 unsafe impl<T: NullTrace> NullTrace for Cell<T> {}
 
 unsafe impl<T: NullTrace> Trace for RefCell<T> {
-    unsafe fn mark(&self) {}
-    unsafe fn manage(&self) {}
+    unsafe fn trace(&self, _: &mut dyn Tracer) {}
     unsafe fn finalize(&mut self) {
         ptr::drop_in_place(self as *mut Self)
     }
@@ -441,11 +492,8 @@ unsafe impl<T: NullTrace> Trace for RefCell<T> {
 unsafe impl<T: NullTrace> NullTrace for RefCell<T> {}
 
 unsafe impl<T: Trace> Trace for PinCell<T> {
-    unsafe fn mark(&self) {
-        self.borrow().mark()
-    }
-    unsafe fn manage(&self) {
-        self.borrow().manage()
+    unsafe fn trace(&self, tracer: &mut dyn Tracer) {
+        self.borrow().trace(tracer)
     }
     unsafe fn finalize(&mut self) {
         self.get_mut().finalize()
@@ -453,3 +501,319 @@ unsafe impl<T: Trace> Trace for PinCell<T> {
 }
 
 unsafe impl<T: NullTrace> NullTrace for PinCell<T> {}
+
+// `set` would put a value no one manages into an object which is managed
+unsafe impl<T: NullTrace> Trace for OnceCell<T> {
+    unsafe fn trace(&self, _: &mut dyn Tracer) {}
+    unsafe fn finalize(&mut self) {
+        ptr::drop_in_place(self as *mut Self)
+    }
+}
+
+unsafe impl<T: NullTrace> NullTrace for OnceCell<T> {}
+
+use std::sync::{Mutex, OnceLock, RwLock};
+
+unsafe impl<T: NullTrace> Trace for Mutex<T> {
+    unsafe fn trace(&self, _: &mut dyn Tracer) {}
+    unsafe fn finalize(&mut self) {
+        ptr::drop_in_place(self as *mut Self)
+    }
+}
+
+unsafe impl<T: NullTrace> NullTrace for Mutex<T> {}
+
+unsafe impl<T: NullTrace> Trace for RwLock<T> {
+    unsafe fn trace(&self, _: &mut dyn Tracer) {}
+    unsafe fn finalize(&mut self) {
+        ptr::drop_in_place(self as *mut Self)
+    }
+}
+
+unsafe impl<T: NullTrace> NullTrace for RwLock<T> {}
+
+// Like `OnceCell`, but `set` can be called from any thread
+unsafe impl<T: NullTrace> Trace for OnceLock<T> {
+    unsafe fn trace(&self, _: &mut dyn Tracer) {}
+    unsafe fn finalize(&mut self) {
+        ptr::drop_in_place(self as *mut Self)
+    }
+}
+
+unsafe impl<T: NullTrace> NullTrace for OnceLock<T> {}
+
+use std::borrow::{Cow, ToOwned};
+use std::cmp::Reverse;
+use std::marker::PhantomData;
+use std::num::{Saturating, Wrapping};
+use std::ops::{Deref, Range, RangeFrom, RangeInclusive, RangeTo, RangeToInclusive};
+use std::pin::Pin;
+
+unsafe impl<T: ?Sized> Trace for PhantomData<T> {
+    unsafe fn trace(&self, _: &mut dyn Tracer) {}
+    unsafe fn finalize(&mut self) {}
+}
+
+unsafe impl<T: ?Sized> NullTrace for PhantomData<T> {}
+
+// A `ManuallyDrop` never drops its contents, so it does not finalize them either
+unsafe impl<T: Trace + ?Sized> Trace for ManuallyDrop<T> {
+    unsafe fn trace(&self, tracer: &mut dyn Tracer) {
+        (**self).trace(tracer)
+    }
+    unsafe fn finalize(&mut self) {}
+}
+
+unsafe impl<T: NullTrace + ?Sized> NullTrace for ManuallyDrop<T> {}
+
+// `Pin` is a transparent wrapper around the pointer
+unsafe impl<P: Trace + Deref> Trace for Pin<P> {
+    unsafe fn trace(&self, tracer: &mut dyn Tracer) {
+        mem::transmute::<&Pin<P>, &P>(self).trace(tracer)
+    }
+    unsafe fn finalize(&mut self) {
+        mem::transmute::<&mut Pin<P>, &mut P>(self).finalize()
+    }
+}
+
+unsafe impl<P: NullTrace + Deref> NullTrace for Pin<P> {}
+
+// Borrowed data is owned, and traced, elsewhere
+unsafe impl<'a, B> Trace for Cow<'a, B>
+where
+    B: ToOwned + ?Sized,
+    B::Owned: Trace,
+{
+    unsafe fn trace(&self, tracer: &mut dyn Tracer) {
+        if let Cow::Owned(owned) = self {
+            owned.trace(tracer)
+        }
+    }
+    unsafe fn finalize(&mut self) {
+        if let Cow::Owned(owned) = self {
+            owned.finalize()
+        }
+    }
+}
+
+unsafe impl<'a, B> NullTrace for Cow<'a, B>
+where
+    B: ToOwned + ?Sized,
+    B::Owned: NullTrace,
+{
+}
+
+macro_rules! trace_wrappers {
+    ($($Type:ident),*) => {$(
+        unsafe impl<T: Trace> Trace for $Type<T> {
+            unsafe fn trace(&self, tracer: &mut dyn Tracer) {
+                self.0.trace(tracer)
+            }
+            unsafe fn finalize(&mut self) {
+                self.0.finalize()
+            }
+        }
+
+        unsafe impl<T: NullTrace> NullTrace for $Type<T> {}
+    )*}
+}
+
+trace_wrappers!(Reverse, Wrapping, Saturating);
+
+unsafe impl<T: Trace> Trace for Range<T> {
+    unsafe fn trace(&self, tracer: &mut dyn Tracer) {
+        self.start.trace(tracer);
+        self.end.trace(tracer);
+    }
+    unsafe fn finalize(&mut self) {
+        self.start.finalize();
+        self.end.finalize();
+    }
+}
+
+unsafe impl<T: NullTrace> NullTrace for Range<T> {}
+
+unsafe impl<T: Trace> Trace for RangeInclusive<T> {
+    unsafe fn trace(&self, tracer: &mut dyn Tracer) {
+        self.start().trace(tracer);
+        self.end().trace(tracer);
+    }
+    unsafe fn finalize(&mut self) {
+        let (mut start, mut end) = ptr::read(self).into_inner();
+        start.finalize();
+        end.finalize();
+        mem::forget((start, end));
+    }
+}
+
+unsafe impl<T: NullTrace> NullTrace for RangeInclusive<T> {}
+
+unsafe impl<T: Trace> Trace for RangeFrom<T> {
+    unsafe fn trace(&self, tracer: &mut dyn Tracer) {
+        self.start.trace(tracer)
+    }
+    unsafe fn finalize(&mut self) {
+        self.start.finalize()
+    }
+}
+
+unsafe impl<T: NullTrace> NullTrace for RangeFrom<T> {}
+
+unsafe impl<T: Trace> Trace for RangeTo<T> {
+    unsafe fn trace(&self, tracer: &mut dyn Tracer) {
+        self.end.trace(tracer)
+    }
+    unsafe fn finalize(&mut self) {
+        self.end.finalize()
+    }
+}
+
+unsafe impl<T: NullTrace> NullTrace for RangeTo<T> {}
+
+unsafe impl<T: Trace> Trace for RangeToInclusive<T> {
+    unsafe fn trace(&self, tracer: &mut dyn Tracer) {
+        self.end.trace(tracer)
+    }
+    unsafe fn finalize(&mut self) {
+        self.end.finalize()
+    }
+}
+
+unsafe impl<T: NullTrace> NullTrace for RangeToInclusive<T> {}
+
+#[cfg(feature = "smallvec")]
+unsafe impl<A: smallvec::Array> Trace for smallvec::SmallVec<A>
+where
+    A::Item: Trace,
+{
+    unsafe fn trace(&self, tracer: &mut dyn Tracer) {
+        for elem in self {
+            elem.trace(tracer);
+        }
+    }
+
+    unsafe fn finalize(&mut self) {
+        for elem in &mut *self {
+            elem.finalize();
+        }
+        self.set_len(0);
+        ptr::drop_in_place(self as *mut Self);
+    }
+}
+
+#[cfg(feature = "smallvec")]
+unsafe impl<A: smallvec::Array> NullTrace for smallvec::SmallVec<A> where A::Item: NullTrace {}
+
+#[cfg(feature = "arrayvec")]
+unsafe impl<T: Trace, const CAP: usize> Trace for arrayvec::ArrayVec<T, CAP> {
+    unsafe fn trace(&self, tracer: &mut dyn Tracer) {
+        for elem in self {
+            elem.trace(tracer);
+        }
+    }
+
+    unsafe fn finalize(&mut self) {
+        for elem in &mut *self {
+            elem.finalize();
+        }
+        self.set_len(0);
+    }
+}
+
+#[cfg(feature = "arrayvec")]
+unsafe impl<T: NullTrace, const CAP: usize> NullTrace for arrayvec::ArrayVec<T, CAP> {}
+
+#[cfg(feature = "arrayvec")]
+unsafe impl<const CAP: usize> Trace for arrayvec::ArrayString<CAP> {
+    unsafe fn trace(&self, _: &mut dyn Tracer) {}
+    unsafe fn finalize(&mut self) {}
+}
+
+#[cfg(feature = "arrayvec")]
+unsafe impl<const CAP: usize> NullTrace for arrayvec::ArrayString<CAP> {}
+
+#[cfg(feature = "indexmap")]
+unsafe impl<T: Trace, S> Trace for indexmap::IndexSet<T, S> {
+    unsafe fn trace(&self, tracer: &mut dyn Tracer) {
+        for elem in self {
+            elem.trace(tracer);
+        }
+    }
+
+    unsafe fn finalize(&mut self) {
+        for mut elem in ptr::read(self) {
+            elem.finalize();
+            mem::forget(elem);
+        }
+    }
+}
+
+#[cfg(feature = "indexmap")]
+unsafe impl<T: NullTrace, S> NullTrace for indexmap::IndexSet<T, S> {}
+
+#[cfg(feature = "indexmap")]
+unsafe impl<K: Trace, V: Trace, S> Trace for indexmap::IndexMap<K, V, S> {
+    unsafe fn trace(&self, tracer: &mut dyn Tracer) {
+        for (key, value) in self {
+            key.trace(tracer);
+            value.trace(tracer);
+        }
+    }
+
+    unsafe fn finalize(&mut self) {
+        for (mut key, mut value) in ptr::read(self) {
+            key.finalize();
+            value.finalize();
+            mem::forget((key, value));
+        }
+    }
+}
+
+#[cfg(feature = "indexmap")]
+unsafe impl<K: NullTrace, V: NullTrace, S> NullTrace for indexmap::IndexMap<K, V, S> {}
+
+#[cfg(feature = "hashbrown")]
+unsafe impl<T: Trace, S> Trace for hashbrown::HashSet<T, S> {
+    unsafe fn trace(&self, tracer: &mut dyn Tracer) {
+        for elem in self {
+            elem.trace(tracer);
+        }
+    }
+
+    unsafe fn finalize(&mut self) {
+        for mut elem in ptr::read(self) {
+            elem.finalize();
+            mem::forget(elem);
+        }
+    }
+}
+
+#[cfg(feature = "hashbrown")]
+unsafe impl<T: NullTrace, S> NullTrace for hashbrown::HashSet<T, S> {}
+
+#[cfg(feature = "hashbrown")]
+unsafe impl<K: Trace, V: Trace, S> Trace for hashbrown::HashMap<K, V, S> {
+    unsafe fn trace(&self, tracer: &mut dyn Tracer) {
+        for (key, value) in self {
+            key.trace(tracer);
+            value.trace(tracer);
+        }
+    }
+
+    unsafe fn finalize(&mut self) {
+        for (mut key, mut value) in ptr::read(self) {
+            key.finalize();
+            value.finalize();
+            mem::forget((key, value));
+        }
+    }
+}
+
+#[cfg(feature = "hashbrown")]
+unsafe impl<K: NullTrace, V: NullTrace, S> NullTrace for hashbrown::HashMap<K, V, S> {}
+
+#[cfg(feature = "bytes")]
+trace_simple!(bytes::Bytes bytes::BytesMut);
+
+#[cfg(feature = "num-bigint")]
+trace_simple!(num_bigint::BigInt num_bigint::BigUint num_bigint::Sign);
@@ -26,5 +26,9 @@ fn main() {
         traced: PinCell::new(GcStore::new(0)),
     });
     *foo.null.borrow_mut() = Null::B(String::new());
-    println!("{}", foo.traced().borrow());
+    println!("{}", foo.traced().borrow().get());
+
+    elise::letroot!(value_root old_root);
+    let old = foo.replace_traced(value_root.gc(1), old_root);
+    println!("{} -> {}", old, foo.traced().borrow().get());
 }
@@ -43,6 +43,53 @@ impl<'root> elise::Finalize for Foo<'root> {
     }
 }
 
+#[derive(elise::GC)]
+struct Pair<'root>(#[gc] GcStore<'root, i32>, #[gc] GcStore<'root, i32>);
+
+#[derive(elise::GC)]
+#[gc(view)]
+enum Value<'root> {
+    Int(i32),
+    Str(#[gc] GcStore<'root, String>),
+    Range {
+        #[gc]
+        start: GcStore<'root, i32>,
+        #[gc]
+        end: GcStore<'root, i32>,
+    },
+}
+
+impl<'root> Value<'root> {
+    fn print(self: Gc<'_, Self>) {
+        if let Some(string) = self.str_0() {
+            println!("{}", string);
+        } else if let (Some(start), Some(end)) = (self.range_start(), self.range_end()) {
+            println!("{}..{}", start, end);
+        } else if let Value::Int(n) = *self {
+            println!("{}", n);
+        }
+    }
+}
+
+// Stands in for a type from another crate, which cannot implement `Trace`
+struct Handle<'root>(GcStore<'root, i32>);
+
+unsafe fn trace_handle(handle: &Handle<'_>, tracer: &mut dyn elise::raw::Tracer) {
+    elise::raw::Trace::trace(&handle.0, tracer)
+}
+
+unsafe fn finalize_handle(handle: &mut Handle<'_>) {
+    elise::raw::Trace::finalize(&mut handle.0)
+}
+
+#[derive(elise::GC)]
+struct Timed<'root> {
+    #[gc(skip)]
+    created: std::time::Instant,
+    #[gc(unsafe_trace_with = trace_handle, finalize_with = finalize_handle)]
+    handle: Handle<'root>,
+}
+
 fn main() {
     {
         elise::letroot!(root);
@@ -52,6 +99,36 @@ fn main() {
         elise::collect();
 
         foo.print_nonlocal();
+
+        elise::letroot!(pair_root value_root int_root str_root);
+
+        let pair = pair_root.gc(Pair(GcStore::new(6), GcStore::new(7)));
+        println!("{}", *pair._0() * *pair._1());
+
+        let value = value_root.gc(Value::Range {
+            start: GcStore::new(0),
+            end: GcStore::new(10),
+        });
+        value.print();
+        int_root.gc(Value::Int(1)).print();
+        str_root
+            .gc(Value::Str(GcStore::new(String::from("str"))))
+            .print();
+
+        elise::letroot!(timed_root);
+
+        let timed = timed_root.gc(Timed {
+            created: std::time::Instant::now(),
+            handle: Handle(GcStore::new(7)),
+        });
+        elise::collect();
+        println!("{:?}", timed.created.elapsed());
+
+        match value.view() {
+            ValueRef::Int(n) => println!("{}", n),
+            ValueRef::Str(string) => println!("{}", string),
+            ValueRef::Range { start, end } => println!("{}", *end - *start),
+        }
     }
 
     elise::collect();
@@ -0,0 +1,41 @@
+use std::any::TypeId;
+
+use gc::Trace;
+
+use crate::raw::Reroot;
+
+/// GC'd objects whose type can be recovered at runtime
+///
+/// This is implemented for every type which is `'static` apart from its root
+/// lifetime. Make it a supertrait of your own trait to downcast its trait
+/// objects with `Gc::downcast`.
+///
+/// # Safety
+///
+/// `Gc::downcast` casts the pointer to whichever type the ids match, so both
+/// methods must return the id of `Self` with its root lifetime set to
+/// `'static`. The blanket implementation is the only one needed.
+pub unsafe trait GcAny: Trace {
+    /// The type id of this object with its root lifetime set to `'static`
+    #[doc(hidden)]
+    fn gc_type_id(&self) -> TypeId;
+
+    #[doc(hidden)]
+    fn static_gc_type_id() -> TypeId
+    where
+        Self: Sized;
+}
+
+unsafe impl<T> GcAny for T
+where
+    T: Trace + Reroot<'static>,
+    T::Rerooted: 'static,
+{
+    fn gc_type_id(&self) -> TypeId {
+        TypeId::of::<T::Rerooted>()
+    }
+
+    fn static_gc_type_id() -> TypeId {
+        TypeId::of::<T::Rerooted>()
+    }
+}
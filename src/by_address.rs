@@ -0,0 +1,67 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash;
+use std::ops::Deref;
+
+use crate::Gc;
+
+/// Compares, orders and hashes a GC pointer by the object it points to
+///
+/// This is the identity of the object, as with `Gc::ptr_eq`, rather than its
+/// value. Hashing and ordering use `Gc::identity`, so they would stay valid
+/// if objects were moved.
+pub struct ByAddress<P>(pub P);
+
+impl<'root, T: ?Sized> ByAddress<Gc<'root, T>> {
+    pub fn into_inner(self) -> Gc<'root, T> {
+        self.0
+    }
+}
+
+impl<P: Copy> Copy for ByAddress<P> {}
+
+impl<P: Clone> Clone for ByAddress<P> {
+    fn clone(&self) -> ByAddress<P> {
+        ByAddress(self.0.clone())
+    }
+}
+
+impl<P> Deref for ByAddress<P> {
+    type Target = P;
+
+    fn deref(&self) -> &P {
+        &self.0
+    }
+}
+
+impl<P: fmt::Debug> fmt::Debug for ByAddress<P> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "ByAddress({:?})", self.0)
+    }
+}
+
+impl<'root, T: ?Sized> PartialEq for ByAddress<Gc<'root, T>> {
+    fn eq(&self, other: &Self) -> bool {
+        Gc::ptr_eq(self.0, other.0)
+    }
+}
+
+impl<'root, T: ?Sized> Eq for ByAddress<Gc<'root, T>> {}
+
+impl<'root, T: ?Sized> PartialOrd for ByAddress<Gc<'root, T>> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<'root, T: ?Sized> Ord for ByAddress<Gc<'root, T>> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        Gc::identity(self.0).cmp(&Gc::identity(other.0))
+    }
+}
+
+impl<'root, T: ?Sized> hash::Hash for ByAddress<Gc<'root, T>> {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        Gc::identity(self.0).hash(state)
+    }
+}
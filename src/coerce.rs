@@ -0,0 +1,37 @@
+/// GC pointers which can be converted to point to an unsized type
+///
+/// This is what `gc_dyn!` is built on; use that instead.
+///
+/// # Safety
+///
+/// `Output` must be the same kind of pointer as `Self`, to the same allocation,
+/// with its target changed to `U`.
+pub unsafe trait Coerce<U: ?Sized> {
+    type Target: ?Sized;
+    type Output;
+
+    /// # Safety
+    ///
+    /// `coerce` must only apply an unsizing coercion to the pointer it is
+    /// given.
+    unsafe fn coerce(self, coerce: impl FnOnce(*const Self::Target) -> *const U) -> Self::Output;
+}
+
+/// Convert a `Gc` or `GcStore` to point to an unsized type, such as a trait
+/// object
+///
+/// ```rust, ignore
+/// let shape: Gc<dyn Shape> = elise::gc_dyn!(square => dyn Shape);
+/// ```
+///
+/// With the `nightly` feature, this conversion also happens implicitly.
+#[macro_export]
+macro_rules! gc_dyn {
+    ($gc:expr => $ty:ty) => {
+        match $gc {
+            // Returning the pointer from a closure annotated with the new type
+            // only allows it to be coerced, never cast
+            gc => unsafe { $crate::raw::Coerce::coerce(gc, |ptr| -> *const $ty { ptr }) },
+        }
+    };
+}
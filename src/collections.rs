@@ -0,0 +1,345 @@
+use std::borrow::Borrow;
+use std::cell::{Ref, RefCell};
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+use std::mem;
+use std::slice;
+
+use gc::{NullTrace, Trace, Tracer};
+
+use crate::raw::Reroot;
+use crate::{Gc, GcStore, Root};
+
+/// Root a pointer which was just moved out of a collection
+fn root_store<'new, T>(root: Root<'new>, store: GcStore<'_, T>) -> Gc<'new, T::Rerooted>
+where
+    T: Reroot<'new> + ?Sized,
+    T::Rerooted: Trace,
+{
+    let gc = unsafe { Gc::rooted(GcStore::raw(&store)) };
+    // The collector owns the pointer, not the store
+    mem::forget(store);
+    root.reroot(gc)
+}
+
+/// A growable array of GC pointers, which can be mutated once it is managed
+///
+/// Values go in as `Gc` pointers, which are traced through the vector from
+/// the moment they are written, and come out rooted by a `Root`. They are
+/// read through `borrow`, which stops the vector from being mutated.
+pub struct GcVec<'root, T: ?Sized + 'root> {
+    inner: RefCell<Vec<GcStore<'root, T>>>,
+}
+
+impl<'root, T: ?Sized> GcVec<'root, T> {
+    pub fn new() -> GcVec<'root, T> {
+        GcVec {
+            inner: RefCell::new(Vec::new()),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.inner.borrow().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inner.borrow().is_empty()
+    }
+
+    /// Borrow the elements, panicking if the vector is being mutated
+    pub fn borrow(&self) -> GcVecRef<'_, 'root, T> {
+        GcVecRef {
+            inner: self.inner.borrow(),
+        }
+    }
+}
+
+impl<'root, T: Trace + ?Sized> GcVec<'root, T> {
+    pub fn push(&self, value: Gc<'root, T>) {
+        self.inner.borrow_mut().push(GcStore::from(value))
+    }
+
+    /// Panics if `index` is greater than the length.
+    pub fn insert(&self, index: usize, value: Gc<'root, T>) {
+        self.inner.borrow_mut().insert(index, GcStore::from(value))
+    }
+
+    pub fn pop<'new>(&self, root: Root<'new>) -> Option<Gc<'new, T::Rerooted>>
+    where
+        T: Reroot<'new>,
+        T::Rerooted: Trace,
+    {
+        let store = self.inner.borrow_mut().pop()?;
+        Some(root_store(root, store))
+    }
+
+    /// Panics if `index` is out of bounds.
+    pub fn remove<'new>(&self, index: usize, root: Root<'new>) -> Gc<'new, T::Rerooted>
+    where
+        T: Reroot<'new>,
+        T::Rerooted: Trace,
+    {
+        let store = self.inner.borrow_mut().remove(index);
+        root_store(root, store)
+    }
+}
+
+impl<'root, T: ?Sized> Default for GcVec<'root, T> {
+    fn default() -> GcVec<'root, T> {
+        GcVec::new()
+    }
+}
+
+/// The elements of a `GcVec`, which cannot be mutated until this is dropped
+///
+/// The pointers it hands out only live as long as it is borrowed, since
+/// nothing keeps an element alive once it is moved out of the vector.
+pub struct GcVecRef<'a, 'root, T: ?Sized + 'root> {
+    inner: Ref<'a, Vec<GcStore<'root, T>>>,
+}
+
+impl<'a, 'root, T: ?Sized> GcVecRef<'a, 'root, T> {
+    pub fn get(&self, index: usize) -> Option<Gc<'_, T>> {
+        let store = self.inner.get(index)?;
+        Some(unsafe { Gc::rooted(GcStore::raw(store)) })
+    }
+
+    pub fn iter(&self) -> GcVecIter<'_, 'root, T> {
+        GcVecIter {
+            inner: self.inner.iter(),
+        }
+    }
+}
+
+/// Iterator over the elements of a borrowed `GcVec`
+pub struct GcVecIter<'a, 'root, T: ?Sized + 'root> {
+    inner: slice::Iter<'a, GcStore<'root, T>>,
+}
+
+impl<'a, 'root, T: ?Sized> Iterator for GcVecIter<'a, 'root, T> {
+    type Item = Gc<'a, T>;
+
+    fn next(&mut self) -> Option<Gc<'a, T>> {
+        let store = self.inner.next()?;
+        Some(unsafe { Gc::rooted(GcStore::raw(store)) })
+    }
+}
+
+unsafe impl<'root, T: Trace + ?Sized> Trace for GcVec<'root, T> {
+    unsafe fn trace(&self, tracer: &mut dyn Tracer) {
+        for store in self.inner.borrow().iter() {
+            store.trace(tracer);
+        }
+    }
+
+    unsafe fn finalize(&mut self) {
+        // Dropping the stores would read objects this sweep may have freed
+        self.inner.get_mut().finalize()
+    }
+}
+
+unsafe impl<'root, 'r, T: Reroot<'root> + ?Sized> Reroot<'root> for GcVec<'r, T> {
+    type Rerooted = GcVec<'root, T::Rerooted>;
+}
+
+/// A double-ended queue of GC pointers, which can be mutated once it is managed
+pub struct GcVecDeque<'root, T: ?Sized + 'root> {
+    inner: RefCell<VecDeque<GcStore<'root, T>>>,
+}
+
+impl<'root, T: ?Sized> GcVecDeque<'root, T> {
+    pub fn new() -> GcVecDeque<'root, T> {
+        GcVecDeque {
+            inner: RefCell::new(VecDeque::new()),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.inner.borrow().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inner.borrow().is_empty()
+    }
+
+    /// Borrow the elements, panicking if the queue is being mutated
+    pub fn borrow(&self) -> GcVecDequeRef<'_, 'root, T> {
+        GcVecDequeRef {
+            inner: self.inner.borrow(),
+        }
+    }
+}
+
+/// The elements of a `GcVecDeque`, which cannot be mutated until this is
+/// dropped
+pub struct GcVecDequeRef<'a, 'root, T: ?Sized + 'root> {
+    inner: Ref<'a, VecDeque<GcStore<'root, T>>>,
+}
+
+impl<'a, 'root, T: ?Sized> GcVecDequeRef<'a, 'root, T> {
+    pub fn get(&self, index: usize) -> Option<Gc<'_, T>> {
+        let store = self.inner.get(index)?;
+        Some(unsafe { Gc::rooted(GcStore::raw(store)) })
+    }
+}
+
+impl<'root, T: Trace + ?Sized> GcVecDeque<'root, T> {
+    pub fn push_back(&self, value: Gc<'root, T>) {
+        self.inner.borrow_mut().push_back(GcStore::from(value))
+    }
+
+    pub fn push_front(&self, value: Gc<'root, T>) {
+        self.inner.borrow_mut().push_front(GcStore::from(value))
+    }
+
+    pub fn pop_back<'new>(&self, root: Root<'new>) -> Option<Gc<'new, T::Rerooted>>
+    where
+        T: Reroot<'new>,
+        T::Rerooted: Trace,
+    {
+        let store = self.inner.borrow_mut().pop_back()?;
+        Some(root_store(root, store))
+    }
+
+    pub fn pop_front<'new>(&self, root: Root<'new>) -> Option<Gc<'new, T::Rerooted>>
+    where
+        T: Reroot<'new>,
+        T::Rerooted: Trace,
+    {
+        let store = self.inner.borrow_mut().pop_front()?;
+        Some(root_store(root, store))
+    }
+}
+
+impl<'root, T: ?Sized> Default for GcVecDeque<'root, T> {
+    fn default() -> GcVecDeque<'root, T> {
+        GcVecDeque::new()
+    }
+}
+
+unsafe impl<'root, T: Trace + ?Sized> Trace for GcVecDeque<'root, T> {
+    unsafe fn trace(&self, tracer: &mut dyn Tracer) {
+        for store in self.inner.borrow().iter() {
+            store.trace(tracer);
+        }
+    }
+
+    unsafe fn finalize(&mut self) {
+        // Dropping the stores would read objects this sweep may have freed
+        self.inner.get_mut().finalize()
+    }
+}
+
+unsafe impl<'root, 'r, T: Reroot<'root> + ?Sized> Reroot<'root> for GcVecDeque<'r, T> {
+    type Rerooted = GcVecDeque<'root, T::Rerooted>;
+}
+
+/// A map from keys to GC pointers, which can be mutated once it is managed
+///
+/// The keys are never traced, so they must not contain GC pointers.
+pub struct GcHashMap<'root, K, V: ?Sized + 'root> {
+    inner: RefCell<HashMap<K, GcStore<'root, V>>>,
+}
+
+impl<'root, K: Eq + Hash, V: ?Sized> GcHashMap<'root, K, V> {
+    pub fn new() -> GcHashMap<'root, K, V> {
+        GcHashMap {
+            inner: RefCell::new(HashMap::new()),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.inner.borrow().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inner.borrow().is_empty()
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.inner.borrow().contains_key(key)
+    }
+
+    pub fn keys(&self) -> Vec<K>
+    where
+        K: Clone,
+    {
+        self.inner.borrow().keys().cloned().collect()
+    }
+
+    /// Borrow the values, panicking if the map is being mutated
+    pub fn borrow(&self) -> GcHashMapRef<'_, 'root, K, V> {
+        GcHashMapRef {
+            inner: self.inner.borrow(),
+        }
+    }
+}
+
+/// The values of a `GcHashMap`, which cannot be mutated until this is dropped
+pub struct GcHashMapRef<'a, 'root, K, V: ?Sized + 'root> {
+    inner: Ref<'a, HashMap<K, GcStore<'root, V>>>,
+}
+
+impl<'a, 'root, K: Eq + Hash, V: ?Sized> GcHashMapRef<'a, 'root, K, V> {
+    pub fn get<Q>(&self, key: &Q) -> Option<Gc<'_, V>>
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        let store = self.inner.get(key)?;
+        Some(unsafe { Gc::rooted(GcStore::raw(store)) })
+    }
+}
+
+impl<'root, K: Eq + Hash, V: Trace + ?Sized> GcHashMap<'root, K, V> {
+    /// Insert a value, leaving any value it replaces to the collector
+    ///
+    /// Use `remove` first to keep the old value.
+    pub fn insert(&self, key: K, value: Gc<'root, V>) {
+        let old = self.inner.borrow_mut().insert(key, GcStore::from(value));
+        mem::forget(old);
+    }
+
+    pub fn remove<'new, Q>(&self, key: &Q, root: Root<'new>) -> Option<Gc<'new, V::Rerooted>>
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+        V: Reroot<'new>,
+        V::Rerooted: Trace,
+    {
+        let store = self.inner.borrow_mut().remove(key)?;
+        Some(root_store(root, store))
+    }
+}
+
+impl<'root, K: Eq + Hash, V: ?Sized> Default for GcHashMap<'root, K, V> {
+    fn default() -> GcHashMap<'root, K, V> {
+        GcHashMap::new()
+    }
+}
+
+unsafe impl<'root, K: NullTrace + Eq + Hash, V: Trace + ?Sized> Trace for GcHashMap<'root, K, V> {
+    unsafe fn trace(&self, tracer: &mut dyn Tracer) {
+        for store in self.inner.borrow().values() {
+            store.trace(tracer);
+        }
+    }
+
+    unsafe fn finalize(&mut self) {
+        // Dropping the stores would read objects this sweep may have freed
+        self.inner.get_mut().finalize()
+    }
+}
+
+unsafe impl<'root, 'r, K, V> Reroot<'root> for GcHashMap<'r, K, V>
+where
+    K: NullTrace + Reroot<'root>,
+    K::Rerooted: Sized,
+    V: Reroot<'root> + ?Sized,
+{
+    type Rerooted = GcHashMap<'root, K::Rerooted, V::Rerooted>;
+}
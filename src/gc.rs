@@ -4,7 +4,10 @@ use std::marker::{PhantomData, PhantomPinned};
 use std::ops::Deref;
 use std::pin::Pin;
 
-use gc::{GcPtr, Trace};
+use gc::{Edges, GcPtr, Trace, Tracer};
+
+use crate::raw::{Coerce, Reroot};
+use crate::{GcAny, GcList, GcRef, GcSlot};
 
 pub struct Gc<'root, T: ?Sized + 'root> {
     ptr: GcPtr<T>,
@@ -18,9 +21,7 @@ impl<'root, T: ?Sized> Clone for Gc<'root, T> {
 }
 
 unsafe impl<'root, T: Trace + ?Sized> Trace for Gc<'root, T> {
-    unsafe fn mark(&self) {}
-
-    unsafe fn manage(&self) {}
+    unsafe fn trace(&self, _: &mut dyn Tracer) {}
 
     unsafe fn finalize(&mut self) {}
 }
@@ -41,6 +42,74 @@ impl<'root, T: ?Sized> Gc<'root, T> {
     pub fn raw(this: Gc<'root, T>) -> GcPtr<T> {
         this.ptr
     }
+
+    /// Move this pointer into a traced `GcList`
+    ///
+    /// The pointer is rooted for as long as the list can be borrowed, so it
+    /// is never left untraced.
+    pub fn push(this: Gc<'root, T>, list: Pin<&mut GcList<'root, T>>) {
+        unsafe { Pin::get_unchecked_mut(list).inner.push(this) }
+    }
+
+    /// Move this pointer into an empty traced `GcSlot`
+    ///
+    /// Panics if `slot` already holds a pointer, which must be moved out with
+    /// `Root::take` first.
+    pub fn put(this: Gc<'root, T>, slot: Pin<&mut GcSlot<'root, T>>) {
+        let slot = unsafe { Pin::get_unchecked_mut(slot) };
+        assert!(slot.is_none(), "Cannot put a Gc into an occupied slot.");
+        slot.inner = Some(this);
+    }
+
+    /// Project this pointer to a field or an element of its object
+    ///
+    /// The `GcRef` keeps the whole object alive, like this pointer does.
+    pub fn map<U: ?Sized>(this: Gc<'root, T>, f: impl FnOnce(&T) -> &U) -> GcRef<'root, U> {
+        GcRef::map(GcRef::from(this), f)
+    }
+
+    /// Tell if both pointers point to the same object
+    ///
+    /// `==` compares the values of the objects instead.
+    pub fn ptr_eq<U: ?Sized>(this: Gc<'root, T>, other: Gc<'_, U>) -> bool {
+        this.ptr.ptr_eq(other.ptr)
+    }
+
+    /// A number identifying this object for as long as it lives
+    ///
+    /// It is handed out the first time it is asked for, and does not depend
+    /// on where the object is in memory.
+    pub fn identity(this: Gc<'root, T>) -> usize {
+        unsafe { this.ptr.identity() }
+    }
+}
+
+impl<'root, T: GcAny + ?Sized> Gc<'root, T> {
+    /// Tell if this object is a `U`, whatever its root lifetime
+    pub fn is<U: GcAny>(this: Gc<'root, T>) -> bool {
+        // Not `this.gc_type_id()`, which would be the id of the `Gc` itself
+        T::gc_type_id(&this) == U::static_gc_type_id()
+    }
+
+    /// Recover the concrete type of this object, if it is a `U`
+    pub fn downcast<U>(this: Gc<'root, T>) -> Option<Gc<'root, U::Rerooted>>
+    where
+        U: GcAny + Reroot<'root>,
+        U::Rerooted: Sized,
+    {
+        if Gc::is::<U>(this) {
+            unsafe { Some(Gc::rooted(this.ptr.cast())) }
+        } else {
+            None
+        }
+    }
+}
+
+impl<'root, T: Trace + ?Sized> Gc<'root, T> {
+    /// List the GC pointers directly owned by this object
+    pub fn edges(this: Gc<'root, T>) -> Edges {
+        unsafe { gc::edges(this.ptr) }
+    }
 }
 
 impl<'root, T: ?Sized> Deref for Gc<'root, T> {
@@ -66,6 +135,23 @@ impl<'root, T: fmt::Display + ?Sized> fmt::Display for Gc<'root, T> {
 
 impl<'root, T: ?Sized> Copy for Gc<'root, T> {}
 
+unsafe impl<'root, T: ?Sized, U: ?Sized + 'root> Coerce<U> for Gc<'root, T> {
+    type Target = T;
+    type Output = Gc<'root, U>;
+
+    unsafe fn coerce(self, coerce: impl FnOnce(*const T) -> *const U) -> Gc<'root, U> {
+        Gc::rooted(self.ptr.coerce(coerce))
+    }
+}
+
+#[cfg(feature = "nightly")]
+impl<'root, T, U> std::ops::CoerceUnsized<Gc<'root, U>> for Gc<'root, T>
+where
+    T: ?Sized + std::marker::Unsize<U>,
+    U: ?Sized,
+{
+}
+
 impl<'root, T: PartialEq + ?Sized> PartialEq for Gc<'root, T> {
     fn eq(&self, rhs: &Self) -> bool {
         unsafe { self.ptr.data() == rhs.ptr.data() }
@@ -0,0 +1,93 @@
+use std::fmt;
+use std::marker::{PhantomData, PhantomPinned};
+use std::mem;
+use std::ops::Deref;
+
+use gc::{ErasedPtr, Trace, Tracer};
+
+use crate::raw::Reroot;
+use crate::Gc;
+
+/// A reference into a GC object, which keeps the whole object alive
+///
+/// It is made by projecting a `Gc` to a field or an element of its object
+/// with `Gc::map`, and is passed around and rerooted like a `Gc`. The type of
+/// its object is erased, so it is never `Send` or `Sync`.
+pub struct GcRef<'root, T: ?Sized + 'root> {
+    object: ErasedPtr,
+    data: *const T,
+    _marker: PhantomData<(&'root T, PhantomPinned)>,
+}
+
+impl<'root, T: ?Sized> GcRef<'root, T> {
+    /// Project this reference further
+    pub fn map<U: ?Sized>(this: GcRef<'root, T>, f: impl FnOnce(&T) -> &U) -> GcRef<'root, U> {
+        GcRef {
+            object: this.object,
+            data: f(&*this),
+            _marker: PhantomData,
+        }
+    }
+
+    /// The object this reference points into
+    pub fn object(this: GcRef<'root, T>) -> ErasedPtr {
+        this.object
+    }
+
+    /// Invariants: the object must be rooted for `'new`
+    pub(crate) unsafe fn reroot<'new>(this: GcRef<'root, T>) -> GcRef<'new, T::Rerooted>
+    where
+        T: Reroot<'new>,
+    {
+        GcRef {
+            object: this.object,
+            data: mem::transmute_copy(&this.data),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<'root, T: ?Sized> From<Gc<'root, T>> for GcRef<'root, T> {
+    fn from(gc: Gc<'root, T>) -> GcRef<'root, T> {
+        GcRef {
+            object: Gc::raw(gc).erase(),
+            data: &*gc,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<'root, T: ?Sized> Clone for GcRef<'root, T> {
+    fn clone(&self) -> GcRef<'root, T> {
+        *self
+    }
+}
+
+impl<'root, T: ?Sized> Copy for GcRef<'root, T> {}
+
+unsafe impl<'root, T: Trace + ?Sized> Trace for GcRef<'root, T> {
+    unsafe fn trace(&self, _: &mut dyn Tracer) {}
+
+    unsafe fn finalize(&mut self) {}
+}
+
+impl<'root, T: ?Sized> Deref for GcRef<'root, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        unsafe { &*self.data }
+    }
+}
+
+impl<'root, T: fmt::Debug + ?Sized> fmt::Debug for GcRef<'root, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let inner: &T = self;
+        write!(f, "GcRef({:?})", inner)
+    }
+}
+
+impl<'root, T: fmt::Display + ?Sized> fmt::Display for GcRef<'root, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        T::fmt(self, f)
+    }
+}
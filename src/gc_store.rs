@@ -1,8 +1,11 @@
 use std::marker::{PhantomData, PhantomPinned};
+use std::mem;
+use std::pin::Pin;
 
-use gc::{GcPtr, Trace};
+use gc::{GcPtr, Trace, Tracer};
 
-use crate::Gc;
+use crate::raw::{Coerce, Reroot};
+use crate::{Gc, GcAny};
 
 pub struct GcStore<'root, T: ?Sized + 'root> {
     ptr: GcPtr<T>,
@@ -18,6 +21,26 @@ impl<'root, T: Trace> GcStore<'root, T> {
     }
 }
 
+impl<'root, T: Trace> GcStore<'root, [T]> {
+    /// Allocate a slice, with the elements stored inline after the header
+    pub fn new_slice(data: impl Into<Vec<T>>) -> GcStore<'root, [T]> {
+        GcStore {
+            ptr: gc::alloc_unmanaged_slice(data.into()),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<'root> GcStore<'root, str> {
+    /// Allocate a copy of a string, stored inline after the header
+    pub fn new_str(data: &str) -> GcStore<'root, str> {
+        GcStore {
+            ptr: gc::alloc_unmanaged_str(data),
+            _marker: PhantomData,
+        }
+    }
+}
+
 impl<'root, T: ?Sized> GcStore<'root, T> {
     pub fn get(&self) -> &T {
         unsafe {
@@ -42,15 +65,44 @@ impl<'root, T: ?Sized> GcStore<'root, T> {
     pub fn raw(this: &GcStore<'root, T>) -> GcPtr<T> {
         this.ptr
     }
+
+    /// Point this store at `value`, returning the pointer it held before
+    ///
+    /// # Safety
+    ///
+    /// The store must be part of a managed object, so that `value` is traced
+    /// through it as soon as it is written. The old pointer is no longer
+    /// traced, so it must be rooted before the next collection if it is used.
+    pub unsafe fn replace(self: Pin<&mut Self>, value: Gc<'_, T>) -> GcPtr<T> {
+        let this = Pin::get_unchecked_mut(self);
+        mem::replace(&mut this.ptr, Gc::raw(value))
+    }
 }
 
-unsafe impl<'root, T: Trace + ?Sized> Trace for GcStore<'root, T> {
-    unsafe fn mark(&self) {
-        self.ptr.mark();
+impl<'root, T: GcAny + ?Sized> GcStore<'root, T> {
+    /// Recover the concrete type of this object, if it is a `U`
+    pub fn downcast<U>(
+        this: GcStore<'root, T>,
+    ) -> Result<GcStore<'root, U::Rerooted>, GcStore<'root, T>>
+    where
+        U: GcAny + Reroot<'root>,
+        U::Rerooted: Sized,
+    {
+        if T::gc_type_id(unsafe { this.ptr.data() }) != U::static_gc_type_id() {
+            return Err(this);
+        }
+        let ptr = unsafe { this.ptr.cast() };
+        mem::forget(this);
+        Ok(GcStore {
+            ptr,
+            _marker: PhantomData,
+        })
     }
+}
 
-    unsafe fn manage(&self) {
-        self.ptr.manage();
+unsafe impl<'root, T: Trace + ?Sized> Trace for GcStore<'root, T> {
+    unsafe fn trace(&self, tracer: &mut dyn Tracer) {
+        self.ptr.trace(tracer);
     }
 
     unsafe fn finalize(&mut self) {}
@@ -65,6 +117,28 @@ impl<'root, T: ?Sized + Trace> From<Gc<'root, T>> for GcStore<'root, T> {
     }
 }
 
+unsafe impl<'root, T: ?Sized, U: ?Sized + 'root> Coerce<U> for GcStore<'root, T> {
+    type Target = T;
+    type Output = GcStore<'root, U>;
+
+    unsafe fn coerce(self, coerce: impl FnOnce(*const T) -> *const U) -> GcStore<'root, U> {
+        let ptr = self.ptr.coerce(coerce);
+        mem::forget(self);
+        GcStore {
+            ptr,
+            _marker: PhantomData,
+        }
+    }
+}
+
+#[cfg(feature = "nightly")]
+impl<'root, T, U> std::ops::CoerceUnsized<GcStore<'root, U>> for GcStore<'root, T>
+where
+    T: ?Sized + std::marker::Unsize<U>,
+    U: ?Sized,
+{
+}
+
 impl<'root, T: ?Sized> Drop for GcStore<'root, T> {
     fn drop(&mut self) {
         unsafe {
@@ -3,12 +3,20 @@
 // the bridge, the only bridge, across what divided us.
 //      - Ursula K. Le Guin
 
+#![cfg_attr(feature = "nightly", feature(coerce_unsized, unsize))]
 #![doc = include_str!("../README.md")]
 
+mod any;
+mod by_address;
+mod coerce;
+mod collections;
 mod gc;
+mod gc_ref;
 mod gc_store;
 mod no_trace;
+mod pin_mutex;
 mod root;
+mod slot;
 mod store;
 
 #[cfg(test)]
@@ -18,17 +26,26 @@ pub use ::gc::collect;
 pub use derive::*;
 
 pub mod raw {
+    pub use crate::coerce::Coerce;
     pub use crate::root::Reroot;
     pub use crate::store::*;
-    pub use gc::{alloc, alloc_unmanaged, manage, GcPtr, Root};
-    pub use gc::{count_managed_objects, count_roots};
-    pub use gc::{NullTrace, Trace};
+    pub use gc::{alloc, alloc_unmanaged, manage, ErasedPtr, GcPtr, Root, State};
+    pub use gc::{alloc_unmanaged_slice, alloc_unmanaged_str};
+    pub use gc::{count_managed_objects, count_roots, live_roots, RootInfo};
+    pub use gc::{edges, retaining_path, Edge, Edges, PathStep, RetainingPath};
+    pub use gc::{trace_field, NullTrace, Trace, Tracer};
 }
 
+pub use self::any::*;
+pub use self::by_address::*;
+pub use self::collections::*;
 pub use self::gc::*;
+pub use self::gc_ref::*;
 pub use self::gc_store::*;
 pub use self::no_trace::*;
-pub use self::root::{HeapRoot, Root};
+pub use self::pin_mutex::*;
+pub use self::root::{HeapRoot, Root, WeakRoot};
+pub use self::slot::*;
 
 pub trait Finalize {
     fn finalize(&mut self);
@@ -1,6 +1,6 @@
 use std::ops::{Deref, DerefMut};
 
-use gc::Trace;
+use gc::{Trace, Tracer};
 
 #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
 pub struct NoTrace<T: ?Sized> {
@@ -40,7 +40,6 @@ impl<T: ?Sized> DerefMut for NoTrace<T> {
 }
 
 unsafe impl<T: ?Sized> Trace for NoTrace<T> {
-    unsafe fn mark(&self) {}
-    unsafe fn manage(&self) {}
+    unsafe fn trace(&self, _: &mut dyn Tracer) {}
     unsafe fn finalize(&mut self) {}
 }
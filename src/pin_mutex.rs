@@ -0,0 +1,112 @@
+use std::fmt;
+use std::ops::Deref;
+use std::pin::Pin;
+use std::sync::{Mutex, MutexGuard, PoisonError, TryLockError};
+
+use gc::{NullTrace, Trace, Tracer};
+
+use crate::raw::Reroot;
+
+/// A mutex which can be locked mutably only while pinned, like `PinCell`
+///
+/// A `Mutex` or a `RwLock` can only hold data without GC pointers, because
+/// anything could be moved out of it while it is locked. The data in a
+/// `PinMutex` is only ever reached as `Pin<&mut T>`, so GC pointers in it
+/// stay where they are traced, and it can be shared between threads.
+///
+/// The collector locks the mutex to trace it, so a thread must not collect
+/// while it holds the lock. A panic while the lock is held does not poison it.
+pub struct PinMutex<T: ?Sized> {
+    inner: Mutex<T>,
+}
+
+impl<T> PinMutex<T> {
+    pub fn new(value: T) -> PinMutex<T> {
+        PinMutex {
+            inner: Mutex::new(value),
+        }
+    }
+
+    pub fn into_inner(self) -> T {
+        self.inner
+            .into_inner()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl<T: ?Sized> PinMutex<T> {
+    /// Lock the mutex, blocking until it is available
+    pub fn lock(self: Pin<&Self>) -> PinMutexGuard<'_, T> {
+        PinMutexGuard {
+            inner: self.get_ref().lock_unpinned(),
+        }
+    }
+
+    /// Lock the mutex if it is available
+    pub fn try_lock(self: Pin<&Self>) -> Option<PinMutexGuard<'_, T>> {
+        let inner = match self.get_ref().inner.try_lock() {
+            Ok(guard) => guard,
+            Err(TryLockError::Poisoned(err)) => err.into_inner(),
+            Err(TryLockError::WouldBlock) => return None,
+        };
+        Some(PinMutexGuard { inner })
+    }
+
+    pub fn get_mut(&mut self) -> &mut T {
+        self.inner.get_mut().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn lock_unpinned(&self) -> MutexGuard<'_, T> {
+        self.inner.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl<T: Default> Default for PinMutex<T> {
+    fn default() -> PinMutex<T> {
+        PinMutex::new(T::default())
+    }
+}
+
+impl<T: fmt::Debug + ?Sized> fmt::Debug for PinMutex<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.inner.try_lock() {
+            Ok(guard) => write!(f, "PinMutex({:?})", &*guard),
+            Err(_) => write!(f, "PinMutex(<locked>)"),
+        }
+    }
+}
+
+/// The lock on a `PinMutex`, which gives pinned mutable access to its data
+pub struct PinMutexGuard<'a, T: ?Sized> {
+    inner: MutexGuard<'a, T>,
+}
+
+impl<'a, T: ?Sized> PinMutexGuard<'a, T> {
+    pub fn as_mut<'b>(this: &'b mut PinMutexGuard<'a, T>) -> Pin<&'b mut T> {
+        unsafe { Pin::new_unchecked(&mut *this.inner) }
+    }
+}
+
+impl<'a, T: ?Sized> Deref for PinMutexGuard<'a, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.inner
+    }
+}
+
+unsafe impl<T: Trace> Trace for PinMutex<T> {
+    unsafe fn trace(&self, tracer: &mut dyn Tracer) {
+        self.lock_unpinned().trace(tracer)
+    }
+
+    unsafe fn finalize(&mut self) {
+        self.get_mut().finalize()
+    }
+}
+
+unsafe impl<T: NullTrace> NullTrace for PinMutex<T> {}
+
+unsafe impl<'root, T: Reroot<'root> + ?Sized> Reroot<'root> for PinMutex<T> {
+    type Rerooted = PinMutex<T::Rerooted>;
+}
@@ -1,14 +1,30 @@
 use std::ops::Deref;
-use std::pin::Pin;
+use std::sync::{Arc, Weak};
 
 use gc::{GcPtr, Root, Trace};
 
 use crate::root::Reroot;
 use crate::Gc;
 
+/// A root which lives on the heap rather than in a stack frame.
+///
+/// Clones share a single entry in the root table: the object stays rooted
+/// until the last `HeapRoot` pointing to it is dropped.
 pub struct HeapRoot<T: ?Sized> {
+    inner: Arc<Inner<T>>,
+}
+
+/// A non-owning handle to a `HeapRoot`.
+///
+/// A `WeakRoot` does not keep its object rooted, it can only be upgraded back
+/// into a `HeapRoot` while some other `HeapRoot` still exists.
+pub struct WeakRoot<T: ?Sized> {
+    inner: Weak<Inner<T>>,
+}
+
+struct Inner<T: ?Sized> {
     #[allow(dead_code)]
-    root: Pin<Box<Root>>,
+    root: Root,
     ptr: GcPtr<T>,
 }
 
@@ -17,6 +33,7 @@ where
     T: Reroot<'root> + Trace,
     T::Rerooted: Trace,
 {
+    #[track_caller]
     pub fn new(data: T) -> HeapRoot<T::Rerooted> {
         unsafe { HeapRoot::make(gc::alloc(data)) }
     }
@@ -27,6 +44,7 @@ where
     T: Reroot<'root> + ?Sized,
     T::Rerooted: Trace,
 {
+    #[track_caller]
     pub fn reroot(gc: Gc<'_, T>) -> HeapRoot<T::Rerooted> {
         unsafe { HeapRoot::make(Gc::raw(gc)) }
     }
@@ -37,28 +55,40 @@ where
     T: Reroot<'root> + ?Sized,
     T::Rerooted: Trace,
 {
+    #[track_caller]
     unsafe fn make(ptr: GcPtr<T>) -> HeapRoot<T::Rerooted> {
         let ptr = super::reroot(ptr);
-        let root = Pin::from(Box::new(Root::new()));
-        Pin::get_ref(Pin::as_ref(&root)).enroot(ptr);
+        let root = Root::new();
         gc::manage::<T::Rerooted>(ptr);
-        HeapRoot { root, ptr }
+        root.enroot(ptr);
+        HeapRoot {
+            inner: Arc::new(Inner { root, ptr }),
+        }
     }
 }
 
 impl<T: ?Sized> HeapRoot<T> {
     pub fn gc<'root>(&'root self) -> Gc<'root, T> {
-        unsafe { Gc::rooted(self.ptr) }
+        unsafe { Gc::rooted(self.inner.ptr) }
+    }
+
+    /// Create a weak handle to this root.
+    pub fn downgrade(this: &HeapRoot<T>) -> WeakRoot<T> {
+        WeakRoot {
+            inner: Arc::downgrade(&this.inner),
+        }
+    }
+
+    /// Count the `HeapRoot`s sharing this root.
+    pub fn strong_count(this: &HeapRoot<T>) -> usize {
+        Arc::strong_count(&this.inner)
     }
 }
 
-impl<T: Trace + ?Sized> Clone for HeapRoot<T> {
+impl<T: ?Sized> Clone for HeapRoot<T> {
     fn clone(&self) -> HeapRoot<T> {
-        let root = Pin::from(Box::new(Root::new()));
-        unsafe { Pin::get_ref(Pin::as_ref(&root)).enroot(self.ptr) };
         HeapRoot {
-            root,
-            ptr: self.ptr,
+            inner: self.inner.clone(),
         }
     }
 }
@@ -66,6 +96,21 @@ impl<T: Trace + ?Sized> Clone for HeapRoot<T> {
 impl<T: ?Sized> Deref for HeapRoot<T> {
     type Target = T;
     fn deref(&self) -> &T {
-        unsafe { self.ptr.data() }
+        unsafe { self.inner.ptr.data() }
+    }
+}
+
+impl<T: ?Sized> WeakRoot<T> {
+    /// Get a `HeapRoot` back, if the object is still rooted by one.
+    pub fn upgrade(&self) -> Option<HeapRoot<T>> {
+        self.inner.upgrade().map(|inner| HeapRoot { inner })
+    }
+}
+
+impl<T: ?Sized> Clone for WeakRoot<T> {
+    fn clone(&self) -> WeakRoot<T> {
+        WeakRoot {
+            inner: self.inner.clone(),
+        }
     }
 }
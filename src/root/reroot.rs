@@ -1,9 +1,11 @@
 use std::cell;
 use std::mem;
+use std::sync;
 
 use gc::{GcPtr, NullTrace, Trace};
 
-use crate::{Gc, GcStore};
+use crate::raw::Rooted;
+use crate::{Gc, GcRef, GcStore};
 
 pub unsafe trait Reroot<'root> {
     type Rerooted: ?Sized + 'root;
@@ -26,10 +28,21 @@ unsafe impl<'root, 'r2, T: Reroot<'root> + ?Sized> Reroot<'root> for Gc<'r2, T>
     type Rerooted = Gc<'root, T::Rerooted>;
 }
 
+unsafe impl<'root, 'r2, T: Reroot<'root> + ?Sized> Reroot<'root> for GcRef<'r2, T> {
+    type Rerooted = GcRef<'root, T::Rerooted>;
+}
+
 unsafe impl<'root, 'r2, T: Reroot<'root> + ?Sized> Reroot<'root> for GcStore<'r2, T> {
     type Rerooted = GcStore<'root, T::Rerooted>;
 }
 
+unsafe impl<'root, 'a: 'root, T: Reroot<'root> + ?Sized> Reroot<'root> for &'a T
+where
+    T::Rerooted: 'a,
+{
+    type Rerooted = &'a T::Rerooted;
+}
+
 unsafe impl<'root, T: Reroot<'root> + ?Sized> Reroot<'root> for pin_cell::PinCell<T> {
     type Rerooted = pin_cell::PinCell<T::Rerooted>;
 }
@@ -38,14 +51,72 @@ unsafe impl<'root, T: NullTrace + Reroot<'root> + ?Sized> Reroot<'root> for cell
     type Rerooted = cell::Cell<T::Rerooted>;
 }
 
+unsafe impl<'root, T: NullTrace + Rooted<'root> + ?Sized> Rooted<'root> for cell::Cell<T> {
+    type Rooted = cell::Cell<T::Rooted>;
+}
+
 unsafe impl<'root, T: NullTrace + Reroot<'root> + ?Sized> Reroot<'root> for cell::RefCell<T> {
     type Rerooted = cell::RefCell<T::Rerooted>;
 }
 
+unsafe impl<'root, T: NullTrace + Rooted<'root> + ?Sized> Rooted<'root> for cell::RefCell<T> {
+    type Rooted = cell::RefCell<T::Rooted>;
+}
+
+unsafe impl<'root, T: NullTrace + Reroot<'root>> Reroot<'root> for cell::OnceCell<T>
+where
+    T::Rerooted: Sized,
+{
+    type Rerooted = cell::OnceCell<T::Rerooted>;
+}
+
+unsafe impl<'root, T: NullTrace + Rooted<'root>> Rooted<'root> for cell::OnceCell<T>
+where
+    T::Rooted: Sized,
+{
+    type Rooted = cell::OnceCell<T::Rooted>;
+}
+
+unsafe impl<'root, T: NullTrace + Reroot<'root> + ?Sized> Reroot<'root> for sync::Mutex<T> {
+    type Rerooted = sync::Mutex<T::Rerooted>;
+}
+
+unsafe impl<'root, T: NullTrace + Rooted<'root> + ?Sized> Rooted<'root> for sync::Mutex<T> {
+    type Rooted = sync::Mutex<T::Rooted>;
+}
+
+unsafe impl<'root, T: NullTrace + Reroot<'root> + ?Sized> Reroot<'root> for sync::RwLock<T> {
+    type Rerooted = sync::RwLock<T::Rerooted>;
+}
+
+unsafe impl<'root, T: NullTrace + Rooted<'root> + ?Sized> Rooted<'root> for sync::RwLock<T> {
+    type Rooted = sync::RwLock<T::Rooted>;
+}
+
+unsafe impl<'root, T: NullTrace + Reroot<'root>> Reroot<'root> for sync::OnceLock<T>
+where
+    T::Rerooted: Sized,
+{
+    type Rerooted = sync::OnceLock<T::Rerooted>;
+}
+
+unsafe impl<'root, T: NullTrace + Rooted<'root>> Rooted<'root> for sync::OnceLock<T>
+where
+    T::Rooted: Sized,
+{
+    type Rooted = sync::OnceLock<T::Rooted>;
+}
+
 macro_rules! reroot_simple {
-    ($($t:ty)*) => {$(unsafe impl<'root> Reroot<'root> for $t {
-        type Rerooted = $t;
-    })*}
+    ($($t:ty)*) => {$(
+        unsafe impl<'root> Reroot<'root> for $t {
+            type Rerooted = $t;
+        }
+
+        unsafe impl<'root> Rooted<'root> for $t {
+            type Rooted = $t;
+        }
+    )*}
 }
 
 reroot_simple!(
@@ -85,23 +156,62 @@ reroot_simple!(
     std::sync::Barrier
     std::sync::Condvar
     std::sync::Once
+    std::sync::atomic::AtomicBool
+    std::sync::atomic::AtomicI8
+    std::sync::atomic::AtomicI16
+    std::sync::atomic::AtomicI32
+    std::sync::atomic::AtomicI64
+    std::sync::atomic::AtomicIsize
+    std::sync::atomic::AtomicU8
+    std::sync::atomic::AtomicU16
+    std::sync::atomic::AtomicU32
+    std::sync::atomic::AtomicU64
+    std::sync::atomic::AtomicUsize
+    i128    u128
+    std::num::NonZeroI8
+    std::num::NonZeroI16
+    std::num::NonZeroI32
+    std::num::NonZeroI64
+    std::num::NonZeroI128
+    std::num::NonZeroIsize
+    std::num::NonZeroU8
+    std::num::NonZeroU16
+    std::num::NonZeroU32
+    std::num::NonZeroU64
+    std::num::NonZeroU128
+    std::num::NonZeroUsize
+    std::ops::RangeFull
+    std::time::Duration
+    std::time::Instant
+    std::time::SystemTime
 );
 
-macro_rules! reroot_arrays {
-    ($($N:expr),*)  => {$(
-        unsafe impl<'root, T: Reroot<'root>> Reroot<'root> for [T; $N]
-            where T::Rerooted: Sized,
-        {
-            type Rerooted = [T::Rerooted; $N];
-        }
-    )*};
+unsafe impl<'root, T: Reroot<'root>, const N: usize> Reroot<'root> for [T; N]
+where
+    T::Rerooted: Sized,
+{
+    type Rerooted = [T::Rerooted; N];
 }
 
-reroot_arrays! {
-    0o00, 0o01, 0o02, 0o03, 0o04, 0o05, 0o06, 0o07,
-    0o10, 0o11, 0o12, 0o13, 0o14, 0o15, 0o16, 0o17,
-    0o20, 0o21, 0o22, 0o23, 0o24, 0o25, 0o26, 0o27,
-    0o30, 0o31, 0o32, 0o33, 0o34, 0o35, 0o36, 0o37
+unsafe impl<'root, T: Rooted<'root>, const N: usize> Rooted<'root> for [T; N]
+where
+    T::Rooted: Sized,
+{
+    type Rooted = [T::Rooted; N];
+}
+
+unsafe impl<'root, T: Reroot<'root>> Reroot<'root> for [T]
+where
+    T::Rerooted: Sized,
+{
+    type Rerooted = [T::Rerooted];
+}
+
+unsafe impl<'root, T: Rooted<'root>> Rooted<'root> for [T]
+where
+    T::Rooted: Sized,
+{
+    type Rooted = [T::Rooted];
 }
 
 macro_rules! reroot_tuples {
@@ -111,6 +221,12 @@ macro_rules! reroot_tuples {
         {
             type Rerooted = ($($T::Rerooted,)*);
         }
+
+        unsafe impl<'root, $($T: Rooted<'root>,)*> Rooted<'root> for ($($T,)*) where
+            $($T::Rooted: Sized,)*
+        {
+            type Rooted = ($($T::Rooted,)*);
+        }
     )*};
 }
 
@@ -128,9 +244,18 @@ reroot_tuples! {
     (A, B, C, D, E, F, G, H, I, J)
     (A, B, C, D, E, F, G, H, I, J, K)
     (A, B, C, D, E, F, G, H, I, J, K, L)
+    (A, B, C, D, E, F, G, H, I, J, K, L, M)
+    (A, B, C, D, E, F, G, H, I, J, K, L, M, N)
+    (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O)
+    (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P)
 }
 
+use std::cmp::Reverse;
 use std::collections::*;
+use std::marker::PhantomData;
+use std::mem::ManuallyDrop;
+use std::num::{Saturating, Wrapping};
+use std::ops::{Range, RangeFrom, RangeInclusive, RangeTo, RangeToInclusive};
 use std::rc::Rc;
 use std::sync::Arc;
 
@@ -141,6 +266,12 @@ macro_rules! reroot_generic {
         {
             type Rerooted = $Type<$($T::Rerooted,)*>;
         }
+
+        unsafe impl<'root, $($T,)*> Rooted<'root> for $Type<$($T,)*> where
+            $($T: ?Sized + Rooted<'root>,)*
+        {
+            type Rooted = $Type<$($T::Rooted,)*>;
+        }
     )*}
 }
 
@@ -152,17 +283,122 @@ macro_rules! reroot_generic_sized {
         {
             type Rerooted = $Type<$($T::Rerooted,)*>;
         }
+
+        unsafe impl<'root, $($T,)*> Rooted<'root> for $Type<$($T,)*> where
+            $($T: Rooted<'root>,)*
+            $($T::Rooted: Sized,)*
+        {
+            type Rooted = $Type<$($T::Rooted,)*>;
+        }
     )*}
 }
 
 reroot_generic! {
-    Box<T>, Rc<T>, Arc<T>
+    Box<T>, Rc<T>, Arc<T>, PhantomData<T>, ManuallyDrop<T>
 }
 
 reroot_generic_sized! {
     Option<T>, Result<T, E>,
     Vec<T>, VecDeque<T>, LinkedList<T>, BinaryHeap<T>,
-    HashMap<K, V, H>, HashSet<T, H>, BTreeMap<K, V>, BTreeSet<T>
+    BTreeMap<K, V>, BTreeSet<T>,
+    Reverse<T>, Wrapping<T>, Saturating<T>,
+    Range<T>, RangeInclusive<T>, RangeFrom<T>, RangeTo<T>, RangeToInclusive<T>
 }
 
-// TODO more impls
+// Hashers hold no GC pointers, so they are kept as they are
+macro_rules! reroot_hashed {
+    ($($krate:ident::$Type:ident<$($T:ident),*>),*) => {$(
+        unsafe impl<'root, $($T,)* S: 'root> Reroot<'root> for $krate::$Type<$($T,)* S> where
+            $($T: Reroot<'root>,)*
+            $($T::Rerooted: Sized,)*
+        {
+            type Rerooted = $krate::$Type<$($T::Rerooted,)* S>;
+        }
+
+        unsafe impl<'root, $($T,)* S: 'root> Rooted<'root> for $krate::$Type<$($T,)* S> where
+            $($T: Rooted<'root>,)*
+            $($T::Rooted: Sized,)*
+        {
+            type Rooted = $krate::$Type<$($T::Rooted,)* S>;
+        }
+    )*}
+}
+
+reroot_hashed! {
+    hash_map::HashMap<K, V>, hash_set::HashSet<T>
+}
+
+unsafe impl<'root, P: Reroot<'root>> Reroot<'root> for std::pin::Pin<P>
+where
+    P::Rerooted: Sized,
+{
+    type Rerooted = std::pin::Pin<P::Rerooted>;
+}
+
+unsafe impl<'root, 'a: 'root, B> Reroot<'root> for std::borrow::Cow<'a, B>
+where
+    B: ToOwned + Reroot<'root> + ?Sized,
+    B::Rerooted: ToOwned + 'a,
+{
+    type Rerooted = std::borrow::Cow<'a, B::Rerooted>;
+}
+
+#[cfg(feature = "smallvec")]
+unsafe impl<'root, A> Reroot<'root> for smallvec::SmallVec<A>
+where
+    A: smallvec::Array + Reroot<'root>,
+    A::Rerooted: smallvec::Array + Sized,
+{
+    type Rerooted = smallvec::SmallVec<A::Rerooted>;
+}
+
+#[cfg(feature = "smallvec")]
+unsafe impl<'root, A> Rooted<'root> for smallvec::SmallVec<A>
+where
+    A: smallvec::Array + Rooted<'root>,
+    A::Rooted: smallvec::Array + Sized,
+{
+    type Rooted = smallvec::SmallVec<A::Rooted>;
+}
+
+#[cfg(feature = "arrayvec")]
+unsafe impl<'root, T: Reroot<'root>, const CAP: usize> Reroot<'root> for arrayvec::ArrayVec<T, CAP>
+where
+    T::Rerooted: Sized,
+{
+    type Rerooted = arrayvec::ArrayVec<T::Rerooted, CAP>;
+}
+
+#[cfg(feature = "arrayvec")]
+unsafe impl<'root, T: Rooted<'root>, const CAP: usize> Rooted<'root> for arrayvec::ArrayVec<T, CAP>
+where
+    T::Rooted: Sized,
+{
+    type Rooted = arrayvec::ArrayVec<T::Rooted, CAP>;
+}
+
+#[cfg(feature = "arrayvec")]
+unsafe impl<'root, const CAP: usize> Reroot<'root> for arrayvec::ArrayString<CAP> {
+    type Rerooted = arrayvec::ArrayString<CAP>;
+}
+
+#[cfg(feature = "arrayvec")]
+unsafe impl<'root, const CAP: usize> Rooted<'root> for arrayvec::ArrayString<CAP> {
+    type Rooted = arrayvec::ArrayString<CAP>;
+}
+
+#[cfg(feature = "indexmap")]
+reroot_hashed! {
+    indexmap::IndexMap<K, V>, indexmap::IndexSet<T>
+}
+
+#[cfg(feature = "hashbrown")]
+reroot_hashed! {
+    hashbrown::HashMap<K, V>, hashbrown::HashSet<T>
+}
+
+#[cfg(feature = "bytes")]
+reroot_simple!(bytes::Bytes bytes::BytesMut);
+
+#[cfg(feature = "num-bigint")]
+reroot_simple!(num_bigint::BigInt num_bigint::BigUint num_bigint::Sign);
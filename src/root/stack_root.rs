@@ -3,7 +3,7 @@ use std::pin::Pin;
 use gc::{GcPtr, Trace};
 
 use crate::root::Reroot;
-use crate::Gc;
+use crate::{Gc, GcList, GcRef, GcSlot};
 
 pub struct Root<'root> {
     root: Pin<&'root mut gc::Root>,
@@ -25,6 +25,20 @@ impl<'root> Root<'root> {
         unsafe { self.make(gc::alloc_unmanaged(data)) }
     }
 
+    /// Allocate a slice, with the elements stored inline after the header
+    pub fn gc_slice<T>(self, data: impl Into<Vec<T>>) -> Gc<'root, [T::Rerooted]>
+    where
+        T: Reroot<'root> + Trace,
+        T::Rerooted: Sized + Trace,
+    {
+        unsafe { self.make(gc::alloc_unmanaged_slice(data.into())) }
+    }
+
+    /// Allocate a copy of a string, stored inline after the header
+    pub fn gc_str(self, data: &str) -> Gc<'root, str> {
+        unsafe { self.make(gc::alloc_unmanaged_str(data)) }
+    }
+
     pub fn reroot<T>(self, gc: Gc<'_, T>) -> Gc<'root, T::Rerooted>
     where
         T: Reroot<'root> + ?Sized,
@@ -33,14 +47,66 @@ impl<'root> Root<'root> {
         unsafe { self.make(Gc::raw(gc)) }
     }
 
+    /// Root the object a `GcRef` points into
+    pub fn reroot_ref<T>(self, gc_ref: GcRef<'_, T>) -> GcRef<'root, T::Rerooted>
+    where
+        T: Reroot<'root> + ?Sized,
+    {
+        unsafe {
+            self.root.enroot_erased(GcRef::object(gc_ref));
+            GcRef::reroot(gc_ref)
+        }
+    }
+
+    /// Move a pointer out of a traced `GcSlot`, rooting it
+    ///
+    /// The pointer is rooted before it leaves `slot`, so it is never left
+    /// untraced in between.
+    pub fn take<T>(self, slot: Pin<&mut GcSlot<'_, T>>) -> Option<Gc<'root, T::Rerooted>>
+    where
+        T: Reroot<'root> + ?Sized,
+        T::Rerooted: Trace,
+    {
+        let slot = unsafe { Pin::get_unchecked_mut(slot) };
+        let gc = self.reroot(slot.inner?);
+        slot.inner = None;
+        Some(gc)
+    }
+
+    /// Move the last pointer out of a traced `GcList`, rooting it
+    pub fn pop<T>(self, list: Pin<&mut GcList<'_, T>>) -> Option<Gc<'root, T::Rerooted>>
+    where
+        T: Reroot<'root> + ?Sized,
+        T::Rerooted: Trace,
+    {
+        let list = unsafe { Pin::get_unchecked_mut(list) };
+        let gc = self.reroot(*list.inner.last()?);
+        list.inner.pop();
+        Some(gc)
+    }
+
+    /// Move the pointer at `index` out of a traced `GcList`, rooting it
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn remove<T>(self, list: Pin<&mut GcList<'_, T>>, index: usize) -> Gc<'root, T::Rerooted>
+    where
+        T: Reroot<'root> + ?Sized,
+        T::Rerooted: Trace,
+    {
+        let list = unsafe { Pin::get_unchecked_mut(list) };
+        let gc = self.reroot(list.inner[index]);
+        list.inner.remove(index);
+        gc
+    }
+
     pub(crate) unsafe fn make<T>(mut self, ptr: GcPtr<T>) -> Gc<'root, T::Rerooted>
     where
         T: Reroot<'root> + ?Sized,
         T::Rerooted: Trace,
     {
         let ptr = super::reroot(ptr);
-        self.emplace(ptr);
         gc::manage::<T::Rerooted>(ptr);
+        self.emplace(ptr);
         Gc::rooted(ptr)
     }
 
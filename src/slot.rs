@@ -0,0 +1,80 @@
+use std::iter::Copied;
+use std::marker::PhantomPinned;
+use std::slice;
+
+use crate::Gc;
+
+/// A traced location holding one GC pointer
+///
+/// The accessor of a `PinCell<GcStore>` or `PinMutex<GcStore>` field reads it
+/// as a cell of `GcField`. Like `GcSlot`, it can only be mutated while pinned
+/// and there is no way to make one, so it is only pointed elsewhere by the
+/// setters the derive generates. The pointer it hands out only lives as long as
+/// it is borrowed.
+#[repr(transparent)]
+pub struct GcField<'root, T: ?Sized + 'root> {
+    inner: Gc<'root, T>,
+    _pinned: PhantomPinned,
+}
+
+impl<'root, T: ?Sized> GcField<'root, T> {
+    pub fn get(&self) -> Gc<'_, T> {
+        self.inner
+    }
+}
+
+/// A traced location holding at most one GC pointer
+///
+/// The accessor of a `PinCell<Option<GcStore>>` or `PinMutex<Option<GcStore>>`
+/// field reads it as a cell of `GcSlot`. It can only be mutated while pinned,
+/// and there is no way to make one, so pointers are only moved out of it with
+/// `Root::take` and into it with `Gc::put`. The pointers it hands out only live
+/// as long as it is borrowed.
+#[repr(transparent)]
+pub struct GcSlot<'root, T: ?Sized + 'root> {
+    pub(crate) inner: Option<Gc<'root, T>>,
+    _pinned: PhantomPinned,
+}
+
+impl<'root, T: ?Sized> GcSlot<'root, T> {
+    pub fn get(&self) -> Option<Gc<'_, T>> {
+        self.inner
+    }
+
+    pub fn is_some(&self) -> bool {
+        self.inner.is_some()
+    }
+
+    pub fn is_none(&self) -> bool {
+        self.inner.is_none()
+    }
+}
+
+/// A traced array of GC pointers
+///
+/// The accessor of a `PinCell<Vec<GcStore>>` or `PinMutex<Vec<GcStore>>` field
+/// reads it as a cell of `GcList`. Like `GcSlot`, pointers are only moved out
+/// of it with `Root::pop` and `Root::remove` and into it with `Gc::push`.
+#[repr(transparent)]
+pub struct GcList<'root, T: ?Sized + 'root> {
+    pub(crate) inner: Vec<Gc<'root, T>>,
+    _pinned: PhantomPinned,
+}
+
+impl<'root, T: ?Sized> GcList<'root, T> {
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    pub fn get(&self, index: usize) -> Option<Gc<'_, T>> {
+        self.inner.get(index).copied()
+    }
+
+    pub fn iter(&self) -> Copied<slice::Iter<'_, Gc<'_, T>>> {
+        self.inner.iter().copied()
+    }
+}
@@ -1,4 +1,10 @@
-use crate::{Gc, GcStore};
+use std::pin::Pin;
+
+use gc::GcPtr;
+
+use crate::{
+    Gc, GcField, GcHashMap, GcList, GcSlot, GcStore, GcVec, GcVecDeque, PinMutex, PinMutexGuard,
+};
 
 pub unsafe trait Store<'root> {
     type Accessor: 'root;
@@ -12,28 +18,166 @@ unsafe impl<'root, 'r, T: ?Sized + 'root> Store<'root> for GcStore<'r, T> {
     }
 }
 
-macro_rules! transmute_store {
+/// The type a traced value is read as from a rooted object
+///
+/// Every `GcStore` in it is read in place as a `Gc`, and everything else is
+/// rerooted. Any container of types implementing it is a `Store`, however
+/// deeply nested.
+///
+/// # Safety
+///
+/// A borrow of `Self` is transmuted to a borrow of `Rooted`, so it must have
+/// the same layout, with only its `GcStore`s read as `Gc`s and its lifetimes
+/// rerooted.
+pub unsafe trait Rooted<'root> {
+    type Rooted: ?Sized + 'root;
+}
+
+unsafe impl<'root, 'r, T: ?Sized + 'root> Rooted<'root> for GcStore<'r, T> {
+    type Rooted = Gc<'root, T>;
+}
+
+unsafe impl<'root, 'r, T: ?Sized + 'root> Rooted<'root> for GcVec<'r, T> {
+    type Rooted = GcVec<'root, T>;
+}
+
+unsafe impl<'root, 'r, T: ?Sized + 'root> Rooted<'root> for GcVecDeque<'r, T> {
+    type Rooted = GcVecDeque<'root, T>;
+}
+
+unsafe impl<'root, 'r, K: 'root, V: ?Sized + 'root> Rooted<'root> for GcHashMap<'r, K, V> {
+    type Rooted = GcHashMap<'root, K, V>;
+}
+
+macro_rules! rooted_store {
+    ($([$($generics:tt)*] $from:ty;)*) => {$(
+        unsafe impl<'root, $($generics)*> Store<'root> for $from
+        where
+            $from: Rooted<'root>,
+        {
+            type Accessor = &'root <$from as Rooted<'root>>::Rooted;
+            unsafe fn rooted(this: &'root $from) -> Self::Accessor {
+                std::mem::transmute_copy(&this)
+            }
+        }
+    )*}
+}
+
+use pin_cell::{PinCell, PinMut};
+use std::collections::*;
+use std::rc::Rc;
+use std::sync::Arc;
+
+rooted_store! {
+    [T: ?Sized] Box<T>;
+    [T: ?Sized] Rc<T>;
+    [T: ?Sized] Arc<T>;
+    [T] Option<T>;
+    [T, E] Result<T, E>;
+    [T] [T];
+    [T, const N: usize] [T; N];
+    [T] Vec<T>;
+    [T] VecDeque<T>;
+    [T] LinkedList<T>;
+    [T] BinaryHeap<T>;
+    [T, S] HashSet<T, S>;
+    [K, V, S] HashMap<K, V, S>;
+    [T] BTreeSet<T>;
+    [K, V] BTreeMap<K, V>;
+    ['r, T: ?Sized] GcVec<'r, T>;
+    ['r, T: ?Sized] GcVecDeque<'r, T>;
+    ['r, K, V: ?Sized] GcHashMap<'r, K, V>;
+    [A, B] (A, B);
+    [A, B, C] (A, B, C);
+    [A, B, C, D] (A, B, C, D);
+    [A, B, C, D, E] (A, B, C, D, E);
+    [A, B, C, D, E, F] (A, B, C, D, E, F);
+    [A, B, C, D, E, F, G] (A, B, C, D, E, F, G);
+    [A, B, C, D, E, F, G, H] (A, B, C, D, E, F, G, H);
+    [A, B, C, D, E, F, G, H, I] (A, B, C, D, E, F, G, H, I);
+    [A, B, C, D, E, F, G, H, I, J] (A, B, C, D, E, F, G, H, I, J);
+    [A, B, C, D, E, F, G, H, I, J, K] (A, B, C, D, E, F, G, H, I, J, K);
+    [A, B, C, D, E, F, G, H, I, J, K, L] (A, B, C, D, E, F, G, H, I, J, K, L);
+    [A, B, C, D, E, F, G, H, I, J, K, L, M] (A, B, C, D, E, F, G, H, I, J, K, L, M);
+    [A, B, C, D, E, F, G, H, I, J, K, L, M, N] (A, B, C, D, E, F, G, H, I, J, K, L, M, N);
+    [A, B, C, D, E, F, G, H, I, J, K, L, M, N, O] (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O);
+    [A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P] (A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P);
+}
+
+// GC'd objects never move, so their cells can be borrowed mutably as pinned
+macro_rules! pin_store {
     ($(for<$($T:ident),*> $from:ty => $to:ty;)*) => {$(
         unsafe impl<'root, 'r, $($T: ?Sized + 'root,)*> Store<'root> for $from {
-            type Accessor = &'root $to;
-            unsafe fn rooted(this: &'root $from) -> &'root $to {
-                std::mem::transmute::<&'root $from, &'root $to>(this)
+            type Accessor = Pin<&'root $to>;
+            unsafe fn rooted(this: &'root $from) -> Pin<&'root $to> {
+                Pin::new_unchecked(std::mem::transmute::<&'root $from, &'root $to>(this))
             }
         }
     )*}
 }
 
-use pin_cell::PinCell;
-use std::collections::*;
+pin_store! {
+    for<T> PinCell<GcStore<'r, T>> => PinCell<GcField<'root, T>>;
+    for<T> PinCell<Option<GcStore<'r, T>>> => PinCell<GcSlot<'root, T>>;
+    for<T> PinCell<Vec<GcStore<'r, T>>> => PinCell<GcList<'root, T>>;
+    for<T> PinMutex<GcStore<'r, T>> => PinMutex<GcField<'root, T>>;
+    for<T> PinMutex<Option<GcStore<'r, T>>> => PinMutex<GcSlot<'root, T>>;
+    for<T> PinMutex<Vec<GcStore<'r, T>>> => PinMutex<GcList<'root, T>>;
+}
+
+/// A traced field holding one GC pointer, which can be overwritten while its
+/// object is rooted
+///
+/// # Safety
+///
+/// `replace` must write `value` where `Trace` finds it, and return the pointer
+/// that was traced there before.
+pub unsafe trait StoreCell<'root>: Store<'root> {
+    type Target: ?Sized + 'root;
+
+    /// Point the field at `value`, returning the pointer it held before
+    ///
+    /// # Safety
+    ///
+    /// `this` must be part of a rooted object. The old pointer is no longer
+    /// traced, so it must be rooted before the next collection if it is used.
+    unsafe fn replace(this: &Self, value: Gc<'root, Self::Target>) -> GcPtr<Self::Target>;
+}
+
+unsafe impl<'root, 'r, T: ?Sized + 'root> StoreCell<'root> for PinCell<GcStore<'r, T>> {
+    type Target = T;
+    unsafe fn replace(this: &Self, value: Gc<'root, T>) -> GcPtr<T> {
+        let mut store = Pin::new_unchecked(this).borrow_mut();
+        GcStore::replace(PinMut::as_mut(&mut store), value)
+    }
+}
+
+unsafe impl<'root, 'r, T: ?Sized + 'root> StoreCell<'root> for PinMutex<GcStore<'r, T>> {
+    type Target = T;
+    unsafe fn replace(this: &Self, value: Gc<'root, T>) -> GcPtr<T> {
+        let mut store = Pin::new_unchecked(this).lock();
+        GcStore::replace(PinMutexGuard::as_mut(&mut store), value)
+    }
+}
+
+#[cfg(feature = "smallvec")]
+rooted_store! {
+    [A: smallvec::Array] smallvec::SmallVec<A>;
+}
+
+#[cfg(feature = "arrayvec")]
+rooted_store! {
+    [T, const N: usize] arrayvec::ArrayVec<T, N>;
+}
+
+#[cfg(feature = "indexmap")]
+rooted_store! {
+    [T, S] indexmap::IndexSet<T, S>;
+    [K, V, S] indexmap::IndexMap<K, V, S>;
+}
 
-transmute_store! {
-    for<T> Box<GcStore<'r, T>> => Box<Gc<'root, T>>;
-    for<T> Option<GcStore<'r, T>> => Option<Gc<'root, T>>;
-    for<T> [GcStore<'r, T>] => [Gc<'root, T>];
-    for<T> Vec<GcStore<'r, T>> => Vec<Gc<'root, T>>;
-    for<T> VecDeque<GcStore<'r, T>> => VecDeque<Gc<'root, T>>;
-    for<T> HashSet<GcStore<'r, T>> => HashSet<Gc<'root, T>>;
-    for<T> BTreeSet<GcStore<'r, T>> => BTreeSet<Gc<'root, T>>;
-    for<T> BinaryHeap<GcStore<'r, T>> => BinaryHeap<Gc<'root, T>>;
-    for<T> PinCell<GcStore<'r, T>> => PinCell<Gc<'root, T>>;
+#[cfg(feature = "hashbrown")]
+rooted_store! {
+    [T, S] hashbrown::HashSet<T, S>;
+    [K, V, S] hashbrown::HashMap<K, V, S>;
 }
@@ -1,8 +1,19 @@
+use std::sync::{Mutex, MutexGuard};
+
 use super::*;
 
+// The collector is global, so tests which collect or count objects must not
+// interleave with each other.
+static LOCK: Mutex<()> = Mutex::new(());
+
+fn serial() -> MutexGuard<'static, ()> {
+    LOCK.lock().unwrap_or_else(|err| err.into_inner())
+}
+
 #[test]
 fn stack_rooted() {
     let _ = env_logger::try_init();
+    let _guard = serial();
     letroot!(root);
     let ptr1 = root.gc(0xBADCAFE);
     assert_eq!(*ptr1, 0xBADCAFE);
@@ -14,6 +25,7 @@ fn stack_rooted() {
 #[test]
 fn rerooting() {
     let _ = env_logger::try_init();
+    let _guard = serial();
 
     {
         letroot!(outer_root);
@@ -50,3 +62,283 @@ fn rerooting() {
     collect();
     assert_eq!(raw::count_managed_objects(), 0);
 }
+
+#[test]
+fn heap_root_clones_share_root() {
+    let _ = env_logger::try_init();
+    let _guard = serial();
+
+    let root = HeapRoot::<i32>::new(0xFEEDBAC);
+    let roots = raw::count_roots();
+
+    let clone = root.clone();
+    assert_eq!(raw::count_roots(), roots);
+    assert_eq!(HeapRoot::strong_count(&root), 2);
+    assert_eq!(&*root as *const i32, &*clone as *const i32);
+
+    drop(root);
+    collect();
+    assert_eq!(raw::count_roots(), roots);
+    assert_eq!(*clone, 0xFEEDBAC);
+
+    drop(clone);
+    assert_eq!(raw::count_roots(), roots - 1);
+}
+
+#[test]
+fn heap_root_downgrade_upgrade() {
+    let _ = env_logger::try_init();
+    let _guard = serial();
+
+    let root = HeapRoot::<i32>::new(0xDEFACED);
+    let weak = HeapRoot::downgrade(&root);
+    let roots = raw::count_roots();
+
+    let upgraded = weak.upgrade().unwrap();
+    assert_eq!(raw::count_roots(), roots);
+    collect();
+    assert_eq!(*upgraded, 0xDEFACED);
+
+    drop(root);
+    drop(upgraded);
+    assert!(weak.upgrade().is_none());
+    assert_eq!(raw::count_roots(), roots - 1);
+}
+
+#[test]
+fn root_provenance() {
+    let _ = env_logger::try_init();
+    let _guard = serial();
+
+    let root = HeapRoot::<String>::new(String::from("leaked?"));
+    let line = line!() - 1;
+    let addr = Gc::raw(root.gc()).erase().addr();
+    let count = raw::count_roots();
+
+    let roots = raw::live_roots();
+    let info = roots
+        .iter()
+        .find(|info| info.addr == addr)
+        .expect("the HeapRoot is not in the root table");
+    assert_eq!(info.type_name, std::any::type_name::<String>());
+
+    if cfg!(debug_assertions) {
+        let location = info.location.unwrap();
+        assert_eq!(location.file(), file!());
+        assert_eq!(location.line(), line);
+        assert!(info.to_string().contains(file!()));
+    }
+
+    drop(root);
+    assert_eq!(raw::count_roots(), count - 1);
+    assert!(raw::live_roots().iter().all(|info| info.addr != addr));
+}
+
+#[test]
+fn retaining_path() {
+    let _ = env_logger::try_init();
+    let _guard = serial();
+
+    let inner = GcStore::new(0xC0FFEE);
+    let target = GcStore::raw(&inner);
+    let outer = GcStore::new(inner);
+    let _root = HeapRoot::<Vec<GcStore<GcStore<i32>>>>::new(vec![outer]);
+
+    let path = unsafe { raw::retaining_path(target) }.unwrap();
+    let types: Vec<_> = path.steps.iter().map(|step| step.type_name).collect();
+    assert_eq!(types.len(), 3);
+    assert!(types[0].starts_with("alloc::vec::Vec<"));
+    assert!(types[1].starts_with("elise::gc_store::GcStore<"));
+    assert_eq!(types[2], "i32");
+    assert_eq!(path.steps[0].addr, path.root.addr);
+    assert!(path.to_string().contains("i32"));
+}
+
+#[test]
+fn edges() {
+    let _ = env_logger::try_init();
+    let _guard = serial();
+
+    letroot!(vec_root pair_root);
+    let shared = GcStore::new(String::from("shared"));
+    let shared_ptr = GcStore::raw(&shared);
+    let vec = vec_root.gc(vec![
+        GcStore::new(Some(GcStore::new(String::from("nested")))),
+        GcStore::new(None),
+    ]);
+    let nested_ptr = GcStore::raw(&vec[0]);
+    let pair = pair_root.gc((shared, nested_ptr));
+
+    let edges: Vec<_> = Gc::edges(vec).collect();
+    assert_eq!(edges.len(), 2);
+    assert!(edges.iter().all(|edge| edge.field.is_none()));
+
+    // Walk the whole graph reachable from the vec
+    let mut seen = std::collections::HashSet::new();
+    let mut stack: Vec<_> = edges.iter().map(|edge| edge.ptr).collect();
+    while let Some(ptr) = stack.pop() {
+        if seen.insert(ptr) {
+            stack.extend(unsafe { ptr.edges() }.map(|edge| edge.ptr));
+        }
+    }
+    assert_eq!(seen.len(), 3);
+
+    let edges: Vec<_> = Gc::edges(pair).map(|edge| edge.ptr).collect();
+    assert_eq!(edges, [shared_ptr.erase(), nested_ptr.erase()]);
+    assert!(seen.contains(&nested_ptr.erase()));
+    assert_eq!(unsafe { edges[0].type_name() }, "alloc::string::String");
+}
+
+#[test]
+fn manage_owned_objects() {
+    let _ = env_logger::try_init();
+    let _guard = serial();
+
+    let before = raw::count_managed_objects();
+    letroot!(root);
+    let _pair = root.gc((GcStore::new(1), GcStore::new(Some(GcStore::new(2)))));
+    assert_eq!(raw::count_managed_objects() - before, 4);
+}
+
+#[test]
+fn unmanaged_store() {
+    let _ = env_logger::try_init();
+    let _guard = serial();
+
+    let rc = std::rc::Rc::new(0);
+    let store = GcStore::new(std::rc::Rc::clone(&rc));
+    assert_eq!(
+        unsafe { GcStore::raw(&store).state() },
+        raw::State::Unmanaged
+    );
+    assert_eq!(**store.get(), 0);
+    assert!(store.get_maybe().is_some());
+
+    // The store owns the object until it is rooted
+    drop(store);
+    assert_eq!(std::rc::Rc::strong_count(&rc), 1);
+}
+
+#[test]
+fn managed_store() {
+    let _ = env_logger::try_init();
+    let _guard = serial();
+
+    letroot!(root);
+    let gc = root.gc(String::from("managed"));
+    let store = GcStore::from(gc);
+    assert_eq!(unsafe { GcStore::raw(&store).state() }, raw::State::Managed);
+    assert!(store.get_maybe().is_none());
+
+    // The collector owns the object, so dropping a store leaves it alone
+    drop(store);
+    collect();
+    assert_eq!(*gc, "managed");
+}
+
+#[test]
+#[should_panic(expected = "after the GcStore has been rooted")]
+fn get_managed_store() {
+    letroot!(root);
+    let store = GcStore::from(root.gc(0));
+    store.get();
+}
+
+/// Holds a store which is only filled in once it is managed
+struct Late {
+    child: std::cell::UnsafeCell<Option<GcStore<'static, String>>>,
+}
+
+unsafe impl raw::Trace for Late {
+    unsafe fn trace(&self, tracer: &mut dyn raw::Tracer) {
+        if let Some(child) = &*self.child.get() {
+            child.trace(tracer);
+        }
+    }
+
+    unsafe fn finalize(&mut self) {}
+}
+
+unsafe impl<'root> raw::Reroot<'root> for Late {
+    type Rerooted = Late;
+}
+
+#[test]
+fn manage_when_marked() {
+    let _ = env_logger::try_init();
+    let _guard = serial();
+
+    letroot!(root);
+    let late = root.gc(Late {
+        child: std::cell::UnsafeCell::new(None),
+    });
+    collect();
+    let count = raw::count_managed_objects();
+    unsafe { *late.child.get() = Some(GcStore::new(String::from("late"))) };
+
+    // The child is reached while marking, and is kept like any other object
+    collect();
+    assert_eq!(raw::count_managed_objects(), count + 1);
+    let child = unsafe { (*late.child.get()).as_ref().unwrap() };
+    assert_eq!(unsafe { GcStore::raw(child).state() }, raw::State::Managed);
+    collect();
+    assert_eq!(*unsafe { Gc::rooted(GcStore::raw(child)) }, "late");
+}
+
+/// Records the state of its child while it is traced
+struct Probe {
+    child: GcStore<'static, String>,
+    seen: std::cell::Cell<Option<(raw::State, bool)>>,
+}
+
+unsafe impl raw::Trace for Probe {
+    unsafe fn trace(&self, tracer: &mut dyn raw::Tracer) {
+        self.child.trace(tracer);
+        let ptr = GcStore::raw(&self.child);
+        self.seen
+            .set(Some((ptr.state(), self.child.get_maybe().is_some())));
+        // Dropping a store to an object being marked must not free it
+        drop(GcStore::from(Gc::rooted(ptr)));
+    }
+
+    unsafe fn finalize(&mut self) {}
+}
+
+unsafe impl<'root> raw::Reroot<'root> for Probe {
+    type Rerooted = Probe;
+}
+
+#[test]
+fn marked_store() {
+    let _ = env_logger::try_init();
+    let _guard = serial();
+
+    letroot!(root);
+    let probe = root.gc(Probe {
+        child: GcStore::new(String::from("child")),
+        seen: std::cell::Cell::new(None),
+    });
+    assert_eq!(probe.seen.get(), Some((raw::State::Managed, false)));
+    collect();
+    assert_eq!(probe.seen.get(), Some((raw::State::Marked, false)));
+
+    // Survivors are unmarked once the collection is over
+    let child = unsafe { Gc::rooted(GcStore::raw(&probe.child)) };
+    assert_eq!(unsafe { Gc::raw(child).state() }, raw::State::Managed);
+    assert_eq!(*child, "child");
+}
+
+#[test]
+fn free_unreachable() {
+    let _ = env_logger::try_init();
+    let _guard = serial();
+
+    let rc = std::rc::Rc::new(0);
+    {
+        letroot!(root);
+        root.gc(std::rc::Rc::clone(&rc));
+    }
+    assert_eq!(std::rc::Rc::strong_count(&rc), 2);
+    collect();
+    assert_eq!(std::rc::Rc::strong_count(&rc), 1);
+}
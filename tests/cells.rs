@@ -0,0 +1,138 @@
+#![feature(arbitrary_self_types)]
+
+use elise::{Gc, GcStore, GC};
+use pin_cell::{PinCell, PinMut};
+
+mod common;
+
+use common::serial;
+
+#[derive(GC)]
+struct Named<'root> {
+    #[gc]
+    name: PinCell<GcStore<'root, String>>,
+}
+
+#[derive(GC)]
+struct Pair<'root>(
+    #[gc] PinCell<GcStore<'root, i32>>,
+    #[gc] PinCell<GcStore<'root, i32>>,
+);
+
+#[derive(GC)]
+struct Slots<'root> {
+    #[gc]
+    slot: PinCell<Option<GcStore<'root, String>>>,
+    #[gc]
+    items: PinCell<Vec<GcStore<'root, String>>>,
+}
+
+#[test]
+fn set_field() {
+    let _guard = serial();
+
+    elise::letroot!(root);
+    let named = root.gc(Named {
+        name: PinCell::new(GcStore::new(String::from("old"))),
+    });
+    {
+        elise::letroot!(name_root);
+        named.set_name(name_root.gc(String::from("new")));
+    }
+    elise::collect();
+    assert_eq!(*named.name().borrow().get(), "new");
+}
+
+#[test]
+#[should_panic(expected = "already borrowed")]
+fn set_while_borrowed() {
+    let _guard = serial();
+
+    elise::letroot!(root name_root);
+    let named = root.gc(Named {
+        name: PinCell::new(GcStore::new(String::from("old"))),
+    });
+    let name = named.name();
+    let name = name.borrow();
+    let old = name.get();
+    // The old value is still reachable through `old`, so the field cannot be
+    // pointed elsewhere
+    named.set_name(name_root.gc(String::from("new")));
+    assert_eq!(*old, "old");
+}
+
+#[test]
+fn replace_field() {
+    let _guard = serial();
+
+    elise::letroot!(root old_root);
+    let pair = root.gc(Pair(
+        PinCell::new(GcStore::new(1)),
+        PinCell::new(GcStore::new(2)),
+    ));
+    let old = {
+        elise::letroot!(value_root);
+        pair.replace_0(value_root.gc(3), old_root)
+    };
+    elise::collect();
+    assert_eq!(*old, 1);
+    assert_eq!(*pair._0().borrow().get(), 3);
+    assert_eq!(*pair._1().borrow().get(), 2);
+}
+
+#[test]
+fn move_between_fields() {
+    let _guard = serial();
+
+    elise::letroot!(root moved_root moved_root_2);
+    let slots = root.gc(Slots {
+        slot: PinCell::new(Some(GcStore::new(String::from("moved")))),
+        items: PinCell::new(vec![]),
+    });
+
+    // Moving the value out of `slot` roots it, rather than leaving it dangling
+    let moved = moved_root.take(PinMut::as_mut(&mut slots.slot().borrow_mut()));
+    assert!(slots.slot().borrow().is_none());
+    elise::collect();
+
+    // Put it into another traced location, and observe it
+    Gc::push(
+        moved.unwrap(),
+        PinMut::as_mut(&mut slots.items().borrow_mut()),
+    );
+    elise::collect();
+    assert_eq!(*slots.items().borrow().get(0).unwrap(), "moved");
+
+    // And back again
+    let moved = moved_root_2.pop(PinMut::as_mut(&mut slots.items().borrow_mut()));
+    Gc::put(
+        moved.unwrap(),
+        PinMut::as_mut(&mut slots.slot().borrow_mut()),
+    );
+    elise::collect();
+    assert!(slots.items().borrow().is_empty());
+    assert_eq!(*slots.slot().borrow().get().unwrap(), "moved");
+}
+
+#[test]
+fn move_out_of_vec() {
+    let _guard = serial();
+
+    elise::letroot!(root popped_root removed_root);
+    let slots = root.gc(Slots {
+        slot: PinCell::new(None),
+        items: PinCell::new(vec![
+            GcStore::new(String::from("first")),
+            GcStore::new(String::from("second")),
+            GcStore::new(String::from("last")),
+        ]),
+    });
+
+    let popped = popped_root.pop(PinMut::as_mut(&mut slots.items().borrow_mut()));
+    let removed = removed_root.remove(PinMut::as_mut(&mut slots.items().borrow_mut()), 0);
+    elise::collect();
+    assert_eq!(*popped.unwrap(), "last");
+    assert_eq!(*removed, "first");
+    assert_eq!(slots.items().borrow().len(), 1);
+    assert_eq!(*slots.items().borrow().get(0).unwrap(), "second");
+}
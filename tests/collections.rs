@@ -0,0 +1,159 @@
+#![feature(arbitrary_self_types)]
+
+use elise::{GcHashMap, GcVec, GcVecDeque, GC};
+
+mod common;
+
+use common::serial;
+
+#[derive(GC)]
+struct Object<'root> {
+    #[gc]
+    array: GcVec<'root, String>,
+    #[gc]
+    queue: GcVecDeque<'root, i32>,
+    #[gc]
+    properties: GcHashMap<'root, String, i32>,
+}
+
+impl<'root> Object<'root> {
+    fn new() -> Object<'root> {
+        Object {
+            array: GcVec::new(),
+            queue: GcVecDeque::new(),
+            properties: GcHashMap::new(),
+        }
+    }
+}
+
+#[test]
+fn vec() {
+    let _guard = serial();
+
+    elise::letroot!(root);
+    let object = root.gc(Object::new());
+    {
+        elise::letroot!(a b c);
+        object.array().push(a.gc(String::from("a")));
+        object.array().push(b.gc(String::from("c")));
+        object.array().insert(1, c.gc(String::from("b")));
+    }
+    elise::collect();
+
+    let array = object.array();
+    assert_eq!(array.len(), 3);
+    {
+        let items = array.borrow();
+        assert_eq!(*items.get(1).unwrap(), "b");
+        assert!(items.get(3).is_none());
+        let strings: Vec<String> = items.iter().map(|s| (*s).clone()).collect();
+        assert_eq!(strings, ["a", "b", "c"]);
+    }
+
+    elise::letroot!(popped_root removed_root);
+    let popped = array.pop(popped_root).unwrap();
+    let removed = array.remove(0, removed_root);
+    elise::collect();
+    assert_eq!(*popped, "c");
+    assert_eq!(*removed, "a");
+    assert_eq!(array.len(), 1);
+    assert_eq!(*array.borrow().get(0).unwrap(), "b");
+}
+
+#[test]
+#[should_panic(expected = "already borrowed")]
+fn remove_while_borrowed() {
+    let _guard = serial();
+
+    elise::letroot!(root);
+    let object = root.gc(Object::new());
+    {
+        elise::letroot!(a);
+        object.array().push(a.gc(String::from("a")));
+    }
+
+    let array = object.array();
+    let items = array.borrow();
+    let first = items.get(0).unwrap();
+    elise::letroot!(removed_root);
+    // The element is still reachable through `first`, so it cannot be moved
+    // out of the vector
+    array.remove(0, removed_root);
+    assert_eq!(*first, "a");
+}
+
+#[test]
+fn vec_deque() {
+    let _guard = serial();
+
+    elise::letroot!(root);
+    let object = root.gc(Object::new());
+    {
+        elise::letroot!(a b);
+        object.queue().push_back(a.gc(2));
+        object.queue().push_front(b.gc(1));
+    }
+    elise::collect();
+
+    let queue = object.queue();
+    assert_eq!(*queue.borrow().get(0).unwrap(), 1);
+
+    elise::letroot!(front_root back_root empty_root);
+    let front = queue.pop_front(front_root).unwrap();
+    let back = queue.pop_back(back_root).unwrap();
+    elise::collect();
+    assert_eq!((*front, *back), (1, 2));
+    assert!(queue.is_empty());
+    assert!(queue.pop_back(empty_root).is_none());
+}
+
+#[test]
+fn hash_map() {
+    let _guard = serial();
+
+    elise::letroot!(root);
+    let object = root.gc(Object::new());
+    {
+        elise::letroot!(x y replaced);
+        object.properties().insert(String::from("x"), x.gc(1));
+        object.properties().insert(String::from("y"), y.gc(2));
+        object
+            .properties()
+            .insert(String::from("x"), replaced.gc(3));
+    }
+    elise::collect();
+
+    let properties = object.properties();
+    assert_eq!(properties.len(), 2);
+    assert!(properties.contains_key("y"));
+    assert_eq!(*properties.borrow().get("x").unwrap(), 3);
+
+    let mut keys = properties.keys();
+    keys.sort();
+    assert_eq!(keys, ["x", "y"]);
+
+    elise::letroot!(removed_root);
+    let removed = properties.remove("y", removed_root).unwrap();
+    elise::collect();
+    assert_eq!(*removed, 2);
+    assert!(properties.borrow().get("y").is_none());
+}
+
+#[test]
+fn free_with_elements() {
+    let _guard = serial();
+
+    {
+        elise::letroot!(root a b c);
+        let a = a.gc(String::from("a"));
+        let b = b.gc(1);
+        let c = c.gc(2);
+        let object = root.gc(Object::new());
+        object.array().push(a);
+        object.queue().push_back(b);
+        object.properties().insert(String::from("c"), c);
+    }
+    // The elements are freed in the same sweep as the collections holding
+    // them, which must not read them as they are freed
+    elise::collect();
+}
@@ -0,0 +1,47 @@
+// Each test crate only uses some of these
+#![allow(dead_code)]
+
+use std::cell::Cell;
+use std::mem;
+use std::sync::{Mutex, MutexGuard};
+
+use elise::raw::{self, Trace, Tracer};
+use elise::GcStore;
+
+// The collector is global, so tests which collect must not interleave with
+// each other.
+static LOCK: Mutex<()> = Mutex::new(());
+
+pub fn serial() -> MutexGuard<'static, ()> {
+    LOCK.lock().unwrap_or_else(|err| err.into_inner())
+}
+
+thread_local! {
+    static FINALIZED: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Counts how many times it is finalized
+#[derive(PartialEq, Eq, Hash)]
+pub struct Counted(pub u32);
+
+unsafe impl Trace for Counted {
+    unsafe fn trace(&self, _: &mut dyn Tracer) {}
+
+    unsafe fn finalize(&mut self) {
+        FINALIZED.with(|count| count.set(count.get() + 1))
+    }
+}
+
+/// How many GC pointers are traced through `value`
+pub fn traced<T: Trace>(value: T) -> usize {
+    let store = GcStore::new(value);
+    unsafe { raw::edges(GcStore::raw(&store)) }.count()
+}
+
+/// Finalize `value` as the collector would, counting the `Counted` in it
+pub fn finalized<T: Trace>(mut value: T) -> usize {
+    FINALIZED.with(|count| count.set(0));
+    unsafe { value.finalize() };
+    mem::forget(value);
+    FINALIZED.with(|count| count.get())
+}
@@ -0,0 +1,167 @@
+#![feature(arbitrary_self_types)]
+#![allow(dead_code, unused_imports)]
+
+use elise::{Gc, GcStore, GC};
+
+mod common;
+
+use common::{finalized, serial, traced, Counted};
+
+#[cfg(feature = "smallvec")]
+#[derive(GC)]
+struct Small<'root> {
+    #[gc]
+    items: smallvec::SmallVec<[GcStore<'root, i32>; 2]>,
+}
+
+#[cfg(feature = "smallvec")]
+#[test]
+fn smallvec() {
+    use smallvec::{smallvec, SmallVec};
+
+    let _guard = serial();
+
+    elise::letroot!(root);
+    let small = root.gc(Small {
+        items: (1..=3).map(GcStore::new).collect(),
+    });
+    elise::collect();
+
+    let items: &SmallVec<[Gc<i32>; 2]> = small.items();
+    assert_eq!(items.iter().map(|n| **n).sum::<i32>(), 6);
+    assert_eq!(Gc::edges(small).count(), 3);
+
+    let inline: SmallVec<[Counted; 4]> = smallvec![Counted(1), Counted(2)];
+    let spilled: SmallVec<[Counted; 1]> = smallvec![Counted(1), Counted(2)];
+    assert_eq!(finalized(inline), 2);
+    assert_eq!(finalized(spilled), 2);
+}
+
+#[cfg(feature = "arrayvec")]
+#[derive(GC)]
+struct Bounded<'root> {
+    #[gc]
+    items: arrayvec::ArrayVec<GcStore<'root, i32>, 4>,
+    name: arrayvec::ArrayString<8>,
+}
+
+#[cfg(feature = "arrayvec")]
+#[test]
+fn arrayvec() {
+    use arrayvec::{ArrayString, ArrayVec};
+
+    let _guard = serial();
+
+    elise::letroot!(root);
+    let bounded = root.gc(Bounded {
+        items: (1..=2).map(GcStore::new).collect(),
+        name: ArrayString::from("bounded").unwrap(),
+    });
+    elise::collect();
+
+    let items: &ArrayVec<Gc<i32>, 4> = bounded.items();
+    assert_eq!(*items[1], 2);
+    assert_eq!(&*bounded.name, "bounded");
+    assert_eq!(Gc::edges(bounded).count(), 2);
+
+    let counted: ArrayVec<Counted, 4> = (0..3).map(Counted).collect();
+    assert_eq!(finalized(counted), 3);
+}
+
+#[cfg(feature = "indexmap")]
+#[derive(GC)]
+struct Indexed<'root> {
+    #[gc]
+    tags: indexmap::IndexSet<GcStore<'root, String>>,
+    #[gc]
+    scores: indexmap::IndexMap<String, GcStore<'root, i32>>,
+}
+
+#[cfg(feature = "indexmap")]
+#[test]
+fn indexmap() {
+    use indexmap::{IndexMap, IndexSet};
+
+    let _guard = serial();
+
+    elise::letroot!(root);
+    let mut scores = IndexMap::new();
+    scores.insert(String::from("a"), GcStore::new(1));
+    scores.insert(String::from("b"), GcStore::new(2));
+    let indexed = root.gc(Indexed {
+        tags: IndexSet::new(),
+        scores,
+    });
+    elise::collect();
+
+    assert!(indexed.tags().is_empty());
+    let scores: &IndexMap<String, Gc<i32>> = indexed.scores();
+    assert_eq!(*scores["b"], 2);
+    assert_eq!(scores.get_index(1).unwrap().0, "b");
+    assert_eq!(Gc::edges(indexed).count(), 2);
+
+    let set: IndexSet<Counted> = (0..3).map(Counted).collect();
+    let map: IndexMap<Counted, Counted> = (0..2).map(|n| (Counted(n), Counted(n))).collect();
+    assert_eq!(finalized(set), 3);
+    assert_eq!(finalized(map), 4);
+}
+
+#[cfg(feature = "hashbrown")]
+#[derive(GC)]
+struct Hashed<'root> {
+    #[gc]
+    tags: hashbrown::HashSet<GcStore<'root, String>>,
+    #[gc]
+    scores: hashbrown::HashMap<String, GcStore<'root, i32>>,
+}
+
+#[cfg(feature = "hashbrown")]
+#[test]
+fn hashbrown() {
+    use hashbrown::{HashMap, HashSet};
+
+    let _guard = serial();
+
+    elise::letroot!(root);
+    let mut scores = HashMap::new();
+    scores.insert(String::from("a"), GcStore::new(1));
+    let hashed = root.gc(Hashed {
+        tags: HashSet::new(),
+        scores,
+    });
+    elise::collect();
+
+    assert!(hashed.tags().is_empty());
+    let scores: &HashMap<String, Gc<i32>> = hashed.scores();
+    assert_eq!(*scores["a"], 1);
+    assert_eq!(Gc::edges(hashed).count(), 1);
+
+    let set: HashSet<Counted> = (0..3).map(Counted).collect();
+    let map: HashMap<Counted, Counted> = (0..2).map(|n| (Counted(n), Counted(n))).collect();
+    assert_eq!(finalized(set), 3);
+    assert_eq!(finalized(map), 4);
+}
+
+#[cfg(feature = "bytes")]
+#[test]
+fn bytes() {
+    use bytes::{Bytes, BytesMut};
+
+    assert_eq!(traced(Bytes::from_static(b"static")), 0);
+    assert_eq!(traced(BytesMut::from(&b"mutable"[..])), 0);
+
+    elise::letroot!(root);
+    let bytes = root.gc(Bytes::from(vec![1, 2, 3]));
+    assert_eq!(&bytes[..], [1, 2, 3]);
+}
+
+#[cfg(feature = "num-bigint")]
+#[test]
+fn num_bigint() {
+    use num_bigint::{BigInt, BigUint};
+
+    elise::letroot!(root);
+    let big = root.gc(BigUint::from(u64::MAX) * 2u32);
+    assert_eq!(big.to_string(), "36893488147419103230");
+    assert_eq!(traced((BigInt::from(-1), BigUint::from(1u32))), 0);
+}
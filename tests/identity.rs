@@ -0,0 +1,51 @@
+use std::collections::{BTreeSet, HashMap};
+
+use elise::{ByAddress, Gc};
+
+#[test]
+fn ptr_eq() {
+    elise::letroot!(a_root b_root);
+    let a = a_root.gc(String::from("same"));
+    let b = b_root.gc(String::from("same"));
+
+    assert_eq!(a, b);
+    assert!(!Gc::ptr_eq(a, b));
+    assert!(Gc::ptr_eq(a, a));
+    assert!(Gc::ptr_eq(a, Gc::clone(&a)));
+}
+
+#[test]
+fn identity() {
+    elise::letroot!(a_root b_root);
+    let a = a_root.gc(0);
+    let b = b_root.gc(0);
+
+    let id = Gc::identity(a);
+    assert_eq!(Gc::identity(a), id);
+    assert_ne!(Gc::identity(b), id);
+    elise::collect();
+    assert_eq!(Gc::identity(a), id);
+}
+
+#[test]
+fn by_address() {
+    elise::letroot!(a_root b_root);
+    let a = a_root.gc(String::from("same"));
+    let b = b_root.gc(String::from("same"));
+
+    let mut map = HashMap::new();
+    map.insert(ByAddress(a), 1);
+    map.insert(ByAddress(b), 2);
+    map.insert(ByAddress(a), 3);
+    assert_eq!(map.len(), 2);
+    assert_eq!(map[&ByAddress(a)], 3);
+    assert_eq!(map[&ByAddress(b)], 2);
+
+    let set: BTreeSet<_> = [ByAddress(b), ByAddress(a), ByAddress(b)].into();
+    assert_eq!(set.len(), 2);
+    assert_eq!(
+        ByAddress(a).cmp(&ByAddress(b)),
+        Gc::identity(a).cmp(&Gc::identity(b))
+    );
+    assert_eq!(***set.iter().next().unwrap(), "same");
+}
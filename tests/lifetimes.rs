@@ -0,0 +1,64 @@
+#![feature(arbitrary_self_types)]
+
+use std::cell::Cell;
+
+use elise::{Finalize, Gc, GcStore, UnsafeFinalize, GC};
+
+mod common;
+
+use common::serial;
+
+#[derive(GC)]
+#[gc(root = 'gc, finalize)]
+struct Named<'gc> {
+    #[gc]
+    name: GcStore<'gc, String>,
+}
+
+impl<'gc> Finalize for Named<'gc> {
+    fn finalize(&mut self) {}
+}
+
+#[test]
+fn custom_root_lifetime() {
+    let _guard = serial();
+
+    elise::letroot!(outer);
+    let named: Gc<Named> = {
+        elise::letroot!(inner);
+        let named = inner.gc(Named {
+            name: GcStore::new(String::from("elise")),
+        });
+        outer.reroot(named)
+    };
+    elise::collect();
+    assert_eq!(*named.name(), "elise");
+}
+
+#[derive(GC)]
+#[gc(root = 'gc, unsafe_finalize)]
+struct Borrowed<'a, 'gc> {
+    counter: &'a Cell<usize>,
+    #[gc]
+    value: GcStore<'gc, i32>,
+}
+
+unsafe impl<'a, 'gc> UnsafeFinalize for Borrowed<'a, 'gc> {
+    fn finalize(&mut self) {}
+}
+
+#[test]
+fn borrowed_lifetime() {
+    let _guard = serial();
+    let counter = Cell::new(0);
+
+    elise::letroot!(root);
+    let borrowed = root.gc(Borrowed {
+        counter: &counter,
+        value: GcStore::new(1),
+    });
+    elise::collect();
+
+    borrowed.counter.set(*borrowed.value() as usize);
+    assert_eq!(counter.get(), 1);
+}
@@ -0,0 +1,87 @@
+#![feature(arbitrary_self_types)]
+
+use std::collections::{BTreeMap, HashMap, LinkedList};
+
+use elise::{Gc, GcStore, GC};
+
+mod common;
+
+use common::serial;
+
+#[derive(GC)]
+struct Leaf<'root> {
+    label: String,
+    #[gc]
+    value: GcStore<'root, i32>,
+}
+
+#[derive(GC)]
+struct Tree<'root> {
+    #[gc]
+    scores: HashMap<String, GcStore<'root, i32>>,
+    #[gc]
+    ordered: BTreeMap<u32, GcStore<'root, String>>,
+    #[gc]
+    list: LinkedList<GcStore<'root, i32>>,
+    #[gc]
+    pair: (GcStore<'root, i32>, GcStore<'root, String>),
+    #[gc]
+    result: Result<GcStore<'root, i32>, String>,
+    #[gc]
+    slots: Vec<Option<GcStore<'root, i32>>>,
+    #[gc]
+    leaves: Vec<Leaf<'root>>,
+}
+
+fn tree<'root>() -> Tree<'root> {
+    Tree {
+        scores: HashMap::from([(String::from("a"), GcStore::new(1))]),
+        ordered: BTreeMap::from([(2, GcStore::new(String::from("two")))]),
+        list: LinkedList::from([GcStore::new(3), GcStore::new(4)]),
+        pair: (GcStore::new(5), GcStore::new(String::from("six"))),
+        result: Ok(GcStore::new(7)),
+        slots: vec![None, Some(GcStore::new(8))],
+        leaves: vec![Leaf {
+            label: String::from("leaf"),
+            value: GcStore::new(9),
+        }],
+    }
+}
+
+#[test]
+fn maps() {
+    let _guard = serial();
+
+    elise::letroot!(root);
+    let tree = root.gc(tree());
+    elise::collect();
+
+    let scores: &HashMap<String, Gc<i32>> = tree.scores();
+    assert_eq!(*scores["a"], 1);
+    let ordered: &BTreeMap<u32, Gc<String>> = tree.ordered();
+    assert_eq!(*ordered[&2], "two");
+}
+
+#[test]
+fn nested() {
+    let _guard = serial();
+
+    elise::letroot!(root);
+    let tree = root.gc(tree());
+    elise::collect();
+
+    let list: &LinkedList<Gc<i32>> = tree.list();
+    assert_eq!(list.iter().map(|n| **n).collect::<Vec<_>>(), [3, 4]);
+    let (five, six): &(Gc<i32>, Gc<String>) = tree.pair();
+    assert_eq!((**five, &***six), (5, "six"));
+    let result: &Result<Gc<i32>, String> = tree.result();
+    assert_eq!(**result.as_ref().unwrap(), 7);
+    let slots: &Vec<Option<Gc<i32>>> = tree.slots();
+    assert!(slots[0].is_none());
+    assert_eq!(*slots[1].unwrap(), 8);
+
+    // Nested objects are rerooted, and keep their own stores
+    let leaves: &Vec<Leaf> = tree.leaves();
+    assert_eq!(leaves[0].label, "leaf");
+    assert_eq!(Gc::edges(tree).count(), 9);
+}
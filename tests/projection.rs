@@ -0,0 +1,61 @@
+use elise::raw;
+use elise::{Gc, GcRef, GC};
+
+mod common;
+
+use common::serial;
+
+#[derive(GC)]
+struct Person {
+    name: String,
+    scores: Vec<i32>,
+}
+
+fn name<'root>(person: GcRef<'root, Person>) -> GcRef<'root, String> {
+    GcRef::map(person, |person| &person.name)
+}
+
+#[test]
+fn map() {
+    let _guard = serial();
+
+    elise::letroot!(root);
+    let person = root.gc(Person {
+        name: String::from("Ada"),
+        scores: vec![3, 5, 8],
+    });
+
+    let scores = Gc::map(person, |person| &person.scores);
+    let second = GcRef::map(scores, |scores| &scores[1]);
+    elise::collect();
+
+    assert_eq!(*name(GcRef::from(person)), "Ada");
+    assert_eq!(*second, 5);
+    assert_eq!(scores.len(), 3);
+    assert_eq!(GcRef::object(second), Gc::raw(person).erase());
+
+    let numbers = Gc::map(person, |person| &person.scores[..]);
+    assert_eq!(&*numbers, &[3, 5, 8]);
+}
+
+#[test]
+fn reroot_ref() {
+    let _guard = serial();
+
+    elise::letroot!(outer);
+    let (name, person_addr) = {
+        elise::letroot!(inner);
+        let person = inner.gc(Person {
+            name: String::from("Grace"),
+            scores: vec![],
+        });
+        let name = Gc::map(person, |person| &person.name);
+        (outer.reroot_ref(name), Gc::raw(person).erase().addr())
+    };
+    elise::collect();
+
+    assert_eq!(*name, "Grace");
+    let roots = raw::live_roots();
+    let root = roots.iter().find(|info| info.addr == person_addr).unwrap();
+    assert!(root.type_name.contains("Person"));
+}
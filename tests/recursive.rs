@@ -0,0 +1,82 @@
+#![feature(arbitrary_self_types)]
+
+use elise::{Gc, GcStore, GC};
+
+mod common;
+
+use common::serial;
+
+#[derive(GC)]
+struct List<'root> {
+    #[gc]
+    #[gc(bound = "")]
+    next: Option<GcStore<'root, List<'root>>>,
+    value: i32,
+}
+
+impl<'root> List<'root> {
+    fn sum(self: Gc<'_, Self>) -> i32 {
+        self.value + self.next().map_or(0, |next| next.sum())
+    }
+}
+
+#[test]
+fn self_recursive() {
+    let _guard = serial();
+    elise::letroot!(root);
+
+    let list = (1..=4).fold(None, |next, value| Some(GcStore::new(List { next, value })));
+    let list = root.gc(List {
+        next: list,
+        value: 0,
+    });
+    elise::collect();
+
+    assert_eq!(list.sum(), 10);
+}
+
+#[derive(GC)]
+#[gc(bound = "")]
+enum Expr<'root> {
+    Lit(i32),
+    Block(#[gc] GcStore<'root, Block<'root>>),
+}
+
+#[derive(GC)]
+#[gc(bound = "")]
+struct Block<'root> {
+    #[gc]
+    exprs: Vec<GcStore<'root, Expr<'root>>>,
+}
+
+impl<'root> Expr<'root> {
+    fn eval(self: Gc<'_, Self>) -> i32 {
+        match self.block_0() {
+            Some(block) => block.exprs().iter().map(|expr| expr.eval()).sum(),
+            None => match *self {
+                Expr::Lit(n) => n,
+                Expr::Block(_) => unreachable!(),
+            },
+        }
+    }
+}
+
+#[test]
+fn mutually_recursive() {
+    let _guard = serial();
+    elise::letroot!(root);
+
+    let inner = Block {
+        exprs: vec![GcStore::new(Expr::Lit(2)), GcStore::new(Expr::Lit(3))],
+    };
+    let outer = Block {
+        exprs: vec![
+            GcStore::new(Expr::Lit(1)),
+            GcStore::new(Expr::Block(GcStore::new(inner))),
+        ],
+    };
+    let expr = root.gc(Expr::Block(GcStore::new(outer)));
+    elise::collect();
+
+    assert_eq!(expr.eval(), 6);
+}
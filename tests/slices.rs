@@ -0,0 +1,87 @@
+#![feature(arbitrary_self_types)]
+
+use elise::raw::Edge;
+use elise::{Gc, GcStore, GC};
+
+mod common;
+
+use common::serial;
+
+#[derive(GC)]
+struct Function<'root> {
+    #[gc]
+    name: GcStore<'root, str>,
+    #[gc]
+    code: GcStore<'root, [u8]>,
+    #[gc]
+    constants: GcStore<'root, [GcStore<'root, String>]>,
+}
+
+#[derive(GC, Debug, PartialEq)]
+#[repr(align(32))]
+struct Aligned(u64);
+
+#[test]
+fn slice() {
+    let _guard = serial();
+
+    elise::letroot!(root);
+    let strings = root.gc_slice(vec![
+        GcStore::new(String::from("a")),
+        GcStore::new(String::from("b")),
+    ]);
+    elise::collect();
+
+    assert_eq!(strings.len(), 2);
+    assert_eq!(unsafe { GcStore::raw(&strings[1]).data() }, "b");
+
+    // Every element is traced
+    let edges: Vec<_> = Gc::edges(strings).map(|edge| edge.ptr).collect();
+    let elements: Vec<_> = strings.iter().map(|s| GcStore::raw(s).erase()).collect();
+    assert_eq!(edges, elements);
+}
+
+#[test]
+fn over_aligned_slice() {
+    let _guard = serial();
+
+    elise::letroot!(root empty_root);
+    let numbers = root.gc_slice([Aligned(1), Aligned(2)]);
+    let empty = empty_root.gc_slice(Vec::<Aligned>::new());
+    elise::collect();
+
+    assert_eq!(&*numbers, &[Aligned(1), Aligned(2)]);
+    assert_eq!(numbers.as_ptr() as usize % 32, 0);
+    assert!(empty.is_empty());
+}
+
+#[test]
+fn str() {
+    let _guard = serial();
+
+    elise::letroot!(root function_root);
+    let s = root.gc_str("hello");
+    assert_eq!(&*s, "hello");
+    assert_eq!(s.to_string(), "hello");
+
+    let function = function_root.gc(Function {
+        name: GcStore::new_str("main"),
+        code: GcStore::new_slice([0x2a, 0x00]),
+        constants: GcStore::new_slice(vec![GcStore::new(String::from("constant"))]),
+    });
+    elise::collect();
+
+    assert_eq!(&*function.name(), "main");
+    assert_eq!(&*function.code(), &[0x2a, 0x00]);
+    let constants = function.constants();
+    assert_eq!(unsafe { GcStore::raw(&constants[0]).data() }, "constant");
+
+    let edges: Vec<Edge> = Gc::edges(function).collect();
+    let types: Vec<_> = edges
+        .iter()
+        .map(|edge| unsafe { edge.ptr.type_name() })
+        .collect();
+    assert_eq!(types[0], "str");
+    assert_eq!(types[1], "[u8]");
+    assert!(types[2].starts_with("[elise::gc_store::GcStore<"));
+}
@@ -0,0 +1,130 @@
+use std::borrow::Cow;
+use std::cell::OnceCell;
+use std::cmp::Reverse;
+use std::marker::PhantomData;
+use std::mem::ManuallyDrop;
+use std::num::{NonZeroU32, Wrapping};
+use std::ops::{Range, RangeInclusive};
+use std::pin::Pin;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use elise::{Gc, GcStore};
+
+mod common;
+
+use common::{finalized, traced, Counted};
+
+fn store() -> GcStore<'static, i32> {
+    GcStore::new(0)
+}
+
+#[test]
+fn pointers() {
+    assert_eq!(traced(Box::new(store())), 1);
+    assert_eq!(traced(vec![store(), store()].into_boxed_slice()), 2);
+    assert_eq!(traced(Rc::new(store())), 1);
+    assert_eq!(traced(Arc::new(store())), 1);
+    assert_eq!(traced(Box::pin(store())), 1);
+
+    assert_eq!(finalized(Box::new(Counted(0))), 1);
+    assert_eq!(
+        finalized(vec![Counted(0), Counted(1)].into_boxed_slice()),
+        2
+    );
+    assert_eq!(finalized(Box::pin(Counted(0))), 1);
+    assert_eq!(finalized(Rc::new(Counted(0))), 1);
+    assert_eq!(finalized(Arc::new(Counted(0))), 1);
+
+    // Shared contents are finalized with their last owner
+    let shared = Rc::new(Counted(0));
+    assert_eq!(finalized(Rc::clone(&shared)), 0);
+    assert_eq!(finalized(shared), 1);
+}
+
+#[test]
+fn wrappers() {
+    assert_eq!(traced(Reverse(store())), 1);
+    assert_eq!(traced(Wrapping(store())), 1);
+    assert_eq!(traced(ManuallyDrop::new(store())), 1);
+    assert_eq!(traced(Cow::<[i32]>::Owned(vec![])), 0);
+    assert_eq!(traced(PhantomData::<GcStore<i32>>), 0);
+    assert_eq!(traced(OnceCell::from(1)), 0);
+
+    assert_eq!(finalized(Reverse(Counted(0))), 1);
+    assert_eq!(finalized(Wrapping(Counted(0))), 1);
+    // Never dropped, so never finalized
+    assert_eq!(finalized(ManuallyDrop::new(Counted(0))), 0);
+    assert_eq!(finalized(Cow::Borrowed("borrowed")), 0);
+    assert_eq!(finalized(Cow::<str>::Owned(String::from("owned"))), 0);
+}
+
+#[test]
+fn ranges() {
+    assert_eq!(
+        traced(Range {
+            start: store(),
+            end: store()
+        }),
+        2
+    );
+    assert_eq!(traced(RangeInclusive::new(store(), store())), 2);
+    assert_eq!(traced(store()..), 1);
+    assert_eq!(traced(..store()), 1);
+    assert_eq!(traced(..=store()), 1);
+    assert_eq!(traced(..), 0);
+
+    assert_eq!(finalized(Counted(0)..Counted(1)), 2);
+    assert_eq!(finalized(Counted(0)..=Counted(1)), 2);
+}
+
+#[test]
+fn arrays_and_tuples() {
+    let array: [GcStore<i32>; 40] = std::array::from_fn(|_| store());
+    assert_eq!(traced(array), 40);
+    assert_eq!(finalized([(); 40].map(|_| Counted(0))), 40);
+
+    #[rustfmt::skip]
+    let tuple = (
+        store(), store(), store(), store(), store(), store(), store(), store(),
+        store(), store(), store(), store(), store(), store(), store(), store(),
+    );
+    assert_eq!(traced(tuple), 16);
+}
+
+#[test]
+fn plain_data() {
+    let data = (
+        u128::MAX,
+        -1i128,
+        NonZeroU32::new(1).unwrap(),
+        Duration::from_secs(1),
+        Instant::now(),
+    );
+    assert_eq!(traced(data), 0);
+    assert_eq!(finalized(Pin::new(Box::new(0u128))), 0);
+}
+
+type Mixed<'root> = (
+    Box<[GcStore<'root, i32>]>,
+    Rc<GcStore<'root, i32>>,
+    Range<GcStore<'root, i32>>,
+    Reverse<i32>,
+);
+
+#[test]
+fn reroot() {
+    elise::letroot!(root);
+    let gc: Gc<Mixed> = root.gc((
+        vec![store()].into_boxed_slice(),
+        Rc::new(store()),
+        store()..store(),
+        Reverse(0),
+    ));
+    assert_eq!(Gc::edges(gc).count(), 4);
+
+    elise::letroot!(array_root);
+    let array = array_root.gc([[0u8; 64]; 2]);
+    assert_eq!(array[1][63], 0);
+}
@@ -0,0 +1,87 @@
+#![feature(arbitrary_self_types)]
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, OnceLock, RwLock};
+use std::thread;
+
+use elise::{Gc, GcStore, HeapRoot, PinMutex, PinMutexGuard, GC};
+
+mod common;
+
+use common::serial;
+
+#[derive(GC)]
+struct Shared<'root> {
+    hits: AtomicUsize,
+    log: Mutex<Vec<String>>,
+    config: RwLock<String>,
+    name: OnceLock<String>,
+    #[gc]
+    latest: PinMutex<GcStore<'root, usize>>,
+    #[gc]
+    history: PinMutex<Vec<GcStore<'root, usize>>>,
+}
+
+#[test]
+fn share_between_threads() {
+    let _guard = serial();
+
+    let shared = HeapRoot::new(Shared {
+        hits: AtomicUsize::new(0),
+        log: Mutex::new(vec![]),
+        config: RwLock::new(String::from("default")),
+        name: OnceLock::new(),
+        latest: PinMutex::new(GcStore::new(0)),
+        history: PinMutex::new(vec![]),
+    });
+
+    thread::scope(|scope| {
+        for n in 1..=4usize {
+            let shared = shared.clone();
+            scope.spawn(move || {
+                let shared: Gc<Shared> = shared.gc();
+                shared.hits.fetch_add(1, Ordering::Relaxed);
+                shared.log.lock().unwrap().push(format!("thread {}", n));
+                let _ = shared.name.set(format!("thread {}", n));
+                *shared.config.write().unwrap() = String::from("updated");
+
+                elise::letroot!(latest_root history_root);
+                shared.set_latest(latest_root.gc(n));
+                let mut history = shared.history().lock();
+                Gc::push(history_root.gc(n), PinMutexGuard::as_mut(&mut history));
+            });
+        }
+    });
+    elise::collect();
+
+    let shared = shared.gc();
+    assert_eq!(shared.hits.load(Ordering::Relaxed), 4);
+    assert_eq!(shared.log.lock().unwrap().len(), 4);
+    assert_eq!(*shared.config.read().unwrap(), "updated");
+    assert!(shared.name.get().unwrap().starts_with("thread "));
+    assert!((1..=4).contains(&*shared.latest().lock().get()));
+
+    let mut history: Vec<usize> = shared.history().lock().iter().map(|n| *n).collect();
+    history.sort();
+    assert_eq!(history, [1, 2, 3, 4]);
+}
+
+#[test]
+fn try_lock() {
+    let _guard = serial();
+
+    elise::letroot!(root);
+    let shared = root.gc(Shared {
+        hits: AtomicUsize::new(0),
+        log: Mutex::new(vec![]),
+        config: RwLock::new(String::new()),
+        name: OnceLock::new(),
+        latest: PinMutex::new(GcStore::new(0)),
+        history: PinMutex::new(vec![]),
+    });
+
+    let latest = shared.latest().lock();
+    assert!(shared.latest().try_lock().is_none());
+    drop(latest);
+    assert!(shared.latest().try_lock().is_some());
+}
@@ -0,0 +1,77 @@
+#![feature(arbitrary_self_types)]
+
+use elise::raw::{ErasedPtr, Trace, Tracer};
+use elise::{GcStore, GC};
+
+mod common;
+
+use common::serial;
+
+#[derive(GC)]
+struct Node<'root> {
+    #[gc]
+    left: GcStore<'root, i32>,
+    #[gc]
+    right: Option<GcStore<'root, i32>>,
+    value: i32,
+}
+
+#[derive(Debug, PartialEq)]
+enum Event {
+    Enter(&'static str),
+    Visit(ErasedPtr),
+    Exit,
+}
+
+/// Records every call made to it while an object is traced
+#[derive(Default)]
+struct Recorder {
+    events: Vec<Event>,
+}
+
+impl Tracer for Recorder {
+    unsafe fn visit(&mut self, ptr: ErasedPtr) {
+        self.events.push(Event::Visit(ptr));
+    }
+
+    fn enter_field(&mut self, name: &'static str) {
+        self.events.push(Event::Enter(name));
+    }
+
+    fn exit_field(&mut self) {
+        self.events.push(Event::Exit);
+    }
+}
+
+#[test]
+fn record_fields() {
+    let _guard = serial();
+
+    elise::letroot!(root);
+    let left = GcStore::new(1);
+    let right = GcStore::new(2);
+    let (left_ptr, right_ptr) = (GcStore::raw(&left).erase(), GcStore::raw(&right).erase());
+    let node = root.gc(Node {
+        left,
+        right: Some(right),
+        value: 3,
+    });
+
+    // The tracer sees each field of the object, but not the objects its
+    // pointers lead to
+    let mut recorder = Recorder::default();
+    unsafe { Trace::trace(&*node, &mut recorder) };
+    assert_eq!(
+        recorder.events,
+        [
+            Event::Enter("left"),
+            Event::Visit(left_ptr),
+            Event::Exit,
+            Event::Enter("right"),
+            Event::Visit(right_ptr),
+            Event::Exit,
+            Event::Enter("value"),
+            Event::Exit,
+        ]
+    );
+}
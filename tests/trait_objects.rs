@@ -0,0 +1,165 @@
+#![feature(arbitrary_self_types)]
+
+use elise::raw::Reroot;
+use elise::{Gc, GcAny, GcStore, GC};
+
+mod common;
+
+use common::serial;
+
+trait Shape: GcAny {
+    fn area(&self) -> i32;
+}
+
+// Inside a `GcStore<'root, dyn Shape>`, the trait object defaults to `'root`
+unsafe impl<'root, 'r> Reroot<'root> for dyn Shape + 'r {
+    type Rerooted = dyn Shape + 'root;
+}
+
+#[derive(GC)]
+struct Square {
+    side: i32,
+}
+
+impl Shape for Square {
+    fn area(&self) -> i32 {
+        self.side * self.side
+    }
+}
+
+#[derive(GC)]
+struct Rect {
+    width: i32,
+    height: i32,
+}
+
+impl Shape for Rect {
+    fn area(&self) -> i32 {
+        self.width * self.height
+    }
+}
+
+#[derive(GC)]
+struct Labeled<'root> {
+    #[gc]
+    label: GcStore<'root, String>,
+    side: i32,
+}
+
+impl<'root> Shape for Labeled<'root> {
+    fn area(&self) -> i32 {
+        self.side * self.side
+    }
+}
+
+#[derive(GC)]
+struct Scene<'root> {
+    #[gc]
+    shapes: Vec<GcStore<'root, dyn Shape>>,
+}
+
+#[test]
+fn coerce_gc() {
+    let _guard = serial();
+
+    elise::letroot!(root);
+    let square = root.gc(Square { side: 3 });
+    let shape: Gc<dyn Shape> = elise::gc_dyn!(square => dyn Shape);
+    elise::collect();
+    assert_eq!(shape.area(), 9);
+}
+
+#[test]
+fn coerce_gc_store() {
+    let _guard = serial();
+
+    elise::letroot!(root);
+    let scene = root.gc(Scene {
+        shapes: vec![
+            elise::gc_dyn!(GcStore::new(Square { side: 2 }) => dyn Shape),
+            elise::gc_dyn!(GcStore::new(Rect { width: 2, height: 3 }) => dyn Shape),
+        ],
+    });
+    elise::collect();
+
+    let areas: Vec<i32> = scene.shapes().iter().map(|shape| shape.area()).collect();
+    assert_eq!(areas, [4, 6]);
+    assert_eq!(Gc::edges(scene).count(), 2);
+}
+
+#[test]
+fn trace_through_trait_object() {
+    let _guard = serial();
+
+    elise::letroot!(root);
+    let labeled = root.gc(Labeled {
+        label: GcStore::new(String::from("square")),
+        side: 4,
+    });
+    let shape: Gc<dyn Shape> = elise::gc_dyn!(labeled => dyn Shape);
+    elise::collect();
+
+    // The allocation still traces its fields as a `Labeled`
+    let edges: Vec<_> = Gc::edges(shape).collect();
+    assert_eq!(edges.len(), 1);
+    assert_eq!(edges[0].field, Some("label"));
+    assert_eq!(shape.area(), 16);
+    assert_eq!(*labeled.label(), "square");
+}
+
+#[test]
+fn downcast_gc() {
+    let _guard = serial();
+
+    elise::letroot!(root);
+    let labeled = root.gc(Labeled {
+        label: GcStore::new(String::from("labeled")),
+        side: 1,
+    });
+    let shape: Gc<dyn Shape> = elise::gc_dyn!(labeled => dyn Shape);
+
+    assert!(Gc::is::<Labeled>(shape));
+    assert!(!Gc::is::<Square>(shape));
+    assert!(Gc::downcast::<Square>(shape).is_none());
+
+    let labeled = Gc::downcast::<Labeled>(shape).unwrap();
+    elise::collect();
+    assert_eq!(*labeled.label(), "labeled");
+}
+
+#[test]
+fn downcast_gc_store() {
+    let _guard = serial();
+
+    let shape: GcStore<dyn Shape> =
+        elise::gc_dyn!(GcStore::new(Rect { width: 1, height: 1 }) => dyn Shape);
+    let shape = match GcStore::downcast::<Square>(shape) {
+        Ok(_) => panic!("a `Rect` was downcast to a `Square`"),
+        Err(shape) => shape,
+    };
+    let rect: GcStore<Rect> = GcStore::downcast::<Rect>(shape).ok().unwrap();
+
+    elise::letroot!(root);
+    let scene = root.gc(Scene {
+        shapes: vec![elise::gc_dyn!(rect => dyn Shape)],
+    });
+    assert_eq!(scene.shapes()[0].area(), 1);
+}
+
+#[cfg(feature = "nightly")]
+#[test]
+fn implicit_coercion() {
+    let _guard = serial();
+
+    elise::letroot!(root);
+    let square: Gc<dyn Shape> = root.gc(Square { side: 5 });
+    assert_eq!(square.area(), 25);
+
+    elise::letroot!(scene_root);
+    let rect: GcStore<dyn Shape> = GcStore::new(Rect {
+        width: 1,
+        height: 2,
+    });
+    let scene = scene_root.gc(Scene { shapes: vec![rect] });
+    assert_eq!(scene.shapes()[0].area(), 2);
+}
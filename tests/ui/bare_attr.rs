@@ -0,0 +1,9 @@
+use elise::GC;
+
+#[derive(GC)]
+#[gc]
+struct Foo {
+    int: i32,
+}
+
+fn main() {}
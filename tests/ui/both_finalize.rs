@@ -0,0 +1,9 @@
+use elise::GC;
+
+#[derive(GC)]
+#[gc(finalize, unsafe_finalize)]
+struct Foo {
+    int: i32,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use elise::{GcStore, GC};
+
+#[derive(GC)]
+struct Foo<'root> {
+    #[gc(finalize)]
+    bar: GcStore<'root, i32>,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use elise::GC;
+
+#[derive(GC)]
+#[gc(finalize)]
+struct Foo<'a> {
+    int: &'a i32,
+}
+
+fn main() {}
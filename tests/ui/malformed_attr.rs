@@ -0,0 +1,9 @@
+use elise::GC;
+
+#[derive(GC)]
+#[gc(finalize null_trace)]
+struct Foo {
+    int: i32,
+}
+
+fn main() {}
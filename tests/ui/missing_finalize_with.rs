@@ -0,0 +1,16 @@
+use elise::raw::{Trace, Tracer};
+use elise::{GcStore, GC};
+
+struct Handle<'root>(GcStore<'root, i32>);
+
+unsafe fn trace_handle(handle: &Handle<'_>, tracer: &mut dyn Tracer) {
+    handle.0.trace(tracer)
+}
+
+#[derive(GC)]
+struct Foo<'root> {
+    #[gc(unsafe_trace_with = trace_handle)]
+    handle: Handle<'root>,
+}
+
+fn main() {}
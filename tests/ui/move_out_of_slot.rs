@@ -0,0 +1,29 @@
+#![feature(arbitrary_self_types)]
+
+use std::pin::Pin;
+
+use elise::{GcStore, GC};
+use pin_cell::{PinCell, PinMut};
+
+#[derive(GC)]
+struct Slots<'root> {
+    #[gc]
+    slot: PinCell<Option<GcStore<'root, i32>>>,
+    #[gc]
+    items: PinCell<Vec<GcStore<'root, i32>>>,
+}
+
+fn main() {
+    elise::letroot!(root);
+    let slots = root.gc(Slots {
+        slot: PinCell::new(Some(GcStore::new(1))),
+        items: PinCell::new(vec![GcStore::new(2)]),
+    });
+
+    // Pointers moved out of a traced location without a root would be left
+    // untraced
+    let mut slot = slots.slot().borrow_mut();
+    let _ = Pin::get_mut(PinMut::as_mut(&mut slot));
+    let mut items = slots.items().borrow_mut();
+    let _ = Pin::get_mut(PinMut::as_mut(&mut items));
+}
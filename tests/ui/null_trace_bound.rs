@@ -0,0 +1,13 @@
+use elise::raw::NullTrace;
+use elise::{GcStore, GC};
+
+// Dropping the field bound would let GC pointers into a `Cell` untraced
+#[derive(GC)]
+#[gc(null_trace, bound(null_trace = ""))]
+struct Wrapper<T: 'static>(T);
+
+fn null_trace<T: NullTrace>() {}
+
+fn main() {
+    null_trace::<Wrapper<GcStore<'static, i32>>>();
+}
@@ -0,0 +1,11 @@
+use std::cell::OnceCell;
+
+use elise::GcStore;
+
+fn main() {
+    elise::letroot!(root);
+    let cell = root.gc(OnceCell::new());
+    // The store would be put into a managed object without being managed
+    let _ = cell.set(GcStore::new(5));
+    elise::collect();
+}
@@ -0,0 +1,13 @@
+use std::sync::OnceLock;
+use std::thread;
+
+use elise::{GcStore, HeapRoot};
+
+fn main() {
+    let lock = HeapRoot::new(OnceLock::new());
+    // The store would be put into a managed object without being managed
+    thread::scope(|scope| {
+        scope.spawn(|| lock.gc().set(GcStore::new(5)));
+    });
+    elise::collect();
+}
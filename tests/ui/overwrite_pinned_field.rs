@@ -0,0 +1,31 @@
+#![feature(arbitrary_self_types)]
+
+use std::pin::Pin;
+
+use elise::{GcStore, PinMutex, PinMutexGuard, GC};
+use pin_cell::{PinCell, PinMut};
+
+#[derive(GC)]
+struct Fields<'root> {
+    #[gc]
+    cell: PinCell<GcStore<'root, i32>>,
+    #[gc]
+    mutex: PinMutex<GcStore<'root, i32>>,
+}
+
+fn main() {
+    elise::letroot!(root cell_root mutex_root);
+    let fields = root.gc(Fields {
+        cell: PinCell::new(GcStore::new(1)),
+        mutex: PinMutex::new(GcStore::new(2)),
+    });
+
+    // Overwriting a field without a setter would leave the old object
+    // reachable from wherever it was read
+    let mut cell = fields.cell().borrow_mut();
+    PinMut::as_mut(&mut cell).set(cell_root.gc(3));
+    let _ = Pin::get_mut(PinMut::as_mut(&mut cell));
+    let mut mutex = fields.mutex().lock();
+    PinMutexGuard::as_mut(&mut mutex).set(mutex_root.gc(4));
+    let _ = Pin::get_mut(PinMutexGuard::as_mut(&mut mutex));
+}
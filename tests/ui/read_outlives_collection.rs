@@ -0,0 +1,24 @@
+#![feature(arbitrary_self_types)]
+
+use elise::{GcVec, GC};
+
+#[derive(GC)]
+struct Object<'root> {
+    #[gc]
+    array: GcVec<'root, String>,
+}
+
+fn main() {
+    elise::letroot!(root element removed_root);
+    let object = root.gc(Object {
+        array: GcVec::new(),
+    });
+    object.array().push(element.gc(String::from("a")));
+
+    // A pointer read from a collection must not outlive the borrow, since the
+    // element could be removed and collected
+    let read = object.array().borrow().get(0).unwrap();
+    object.array().remove(0, removed_root);
+    elise::collect();
+    assert_eq!(*read, "a");
+}
@@ -0,0 +1,29 @@
+#![feature(arbitrary_self_types)]
+
+use elise::{GcStore, PinMutex, GC};
+use pin_cell::PinCell;
+
+#[derive(GC)]
+struct Fields<'root> {
+    #[gc]
+    cell: PinCell<GcStore<'root, i32>>,
+    #[gc]
+    mutex: PinMutex<GcStore<'root, i32>>,
+}
+
+fn main() {
+    elise::letroot!(root cell_root mutex_root);
+    let fields = root.gc(Fields {
+        cell: PinCell::new(GcStore::new(1)),
+        mutex: PinMutex::new(GcStore::new(2)),
+    });
+
+    // A pointer read from a field must not outlive the borrow, since a setter
+    // could point the field elsewhere and the old object be collected
+    let cell = fields.cell().borrow().get();
+    let mutex = fields.mutex().lock().get();
+    fields.set_cell(cell_root.gc(3));
+    fields.set_mutex(mutex_root.gc(4));
+    elise::collect();
+    assert_eq!((*cell, *mutex), (1, 2));
+}
@@ -0,0 +1,24 @@
+#![feature(arbitrary_self_types)]
+
+use elise::{GcStore, GC};
+use pin_cell::{PinCell, PinMut};
+
+#[derive(GC)]
+struct Slots<'root> {
+    #[gc]
+    slot: PinCell<Option<GcStore<'root, i32>>>,
+}
+
+fn main() {
+    elise::letroot!(root take_root);
+    let slots = root.gc(Slots {
+        slot: PinCell::new(Some(GcStore::new(1))),
+    });
+
+    // A pointer read from a slot must not outlive the borrow, since the slot
+    // could be emptied and the object collected
+    let read = slots.slot().borrow().get().unwrap();
+    take_root.take(PinMut::as_mut(&mut slots.slot().borrow_mut()));
+    elise::collect();
+    assert_eq!(*read, 1);
+}
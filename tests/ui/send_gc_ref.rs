@@ -0,0 +1,23 @@
+use std::rc::Rc;
+use std::thread;
+
+use elise::{Gc, GC};
+
+#[derive(GC)]
+struct Shared {
+    count: i32,
+    // Not thread safe, so no projection into it can be sent either
+    owner: Rc<String>,
+}
+
+fn main() {
+    elise::letroot!(root);
+    let shared = root.gc(Shared {
+        count: 0,
+        owner: Rc::new(String::from("main")),
+    });
+    let count = Gc::map(shared, |shared| &shared.count);
+    thread::scope(|scope| {
+        scope.spawn(move || *count);
+    });
+}
@@ -0,0 +1,10 @@
+use elise::{GcStore, GC};
+
+#[derive(GC)]
+#[gc(root = 'gc)]
+struct Foo<'root> {
+    #[gc]
+    bar: GcStore<'root, i32>,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use elise::{GcStore, GC};
+
+#[derive(GC)]
+enum Foo<'root> {
+    #[gc]
+    Bar(GcStore<'root, i32>),
+}
+
+fn main() {}
@@ -0,0 +1,46 @@
+#![feature(arbitrary_self_types)]
+
+use elise::{Gc, GcStore, GC};
+
+mod common;
+
+use common::serial;
+
+#[derive(GC)]
+#[gc(view)]
+enum Value<'root> {
+    Int(i32),
+    Str(#[gc] GcStore<'root, String>),
+    Range {
+        #[gc]
+        start: GcStore<'root, i32>,
+        #[gc]
+        end: GcStore<'root, i32>,
+    },
+}
+
+fn describe(value: &Gc<'_, Value<'_>>) -> String {
+    match value.view() {
+        ValueRef::Int(n) => n.to_string(),
+        ValueRef::Str(string) => (*string).clone(),
+        ValueRef::Range { start, end } => format!("{}..{}", *start, *end),
+    }
+}
+
+#[test]
+fn view_variants() {
+    let _guard = serial();
+
+    elise::letroot!(int str range);
+    let int = int.gc(Value::Int(1));
+    let str = str.gc(Value::Str(GcStore::new(String::from("two"))));
+    let range = range.gc(Value::Range {
+        start: GcStore::new(3),
+        end: GcStore::new(4),
+    });
+    elise::collect();
+
+    assert_eq!(describe(&int), "1");
+    assert_eq!(describe(&str), "two");
+    assert_eq!(describe(&range), "3..4");
+}